@@ -1,37 +1,248 @@
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Schema, SchemaObject};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use crate::domain::models::{ColumnMetadata, ExportData, ExportFormat, ExportOptions};
+use crate::domain::models::{
+    ColumnMetadata, ColumnType, ExportData, ExportFormat, ExportOptions, Sheet,
+};
+
+/// How the client should present the exported file (`Content-Disposition` type)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Disposition {
+    /// Download the file (default)
+    #[default]
+    Attachment,
+    /// Display the file in the browser when possible (e.g. PDF preview)
+    Inline,
+}
+
+impl Disposition {
+    pub const ALL: [Disposition; 2] = [Disposition::Attachment, Disposition::Inline];
+
+    /// Parse a disposition name as sent by clients (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|disposition| disposition.as_str().eq_ignore_ascii_case(name))
+    }
+
+    /// Get the `Content-Disposition` type
+    pub fn as_str(&self) -> &str {
+        match self {
+            Disposition::Attachment => "attachment",
+            Disposition::Inline => "inline",
+        }
+    }
+}
+
+/// Where to deliver the export instead of returning it in the response
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Destination {
+    /// URL (e.g. pre-signed) the file is uploaded to with `PUT`; the host must be allowed
+    pub put_url: String,
+}
 
 /// HTTP request DTO
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ExportRequest {
     pub title: String,
+    /// Export format name (case-insensitive)
+    #[schemars(schema_with = "format_schema")]
     pub format: String,
     pub headers: Vec<String>,
     pub rows: Vec<Vec<String>>,
+    /// Set to false when `rows` carry no header row; `headers` must then be empty and
+    /// `Column 1`, `Column 2`, ... are generated
+    #[serde(default)]
+    pub has_header: Option<bool>,
     #[serde(default)]
     pub options: Option<ExportOptions>,
     /// Optional column metadata for proper formatting (alignment, width hints)
     #[serde(default)]
     pub column_metadata: Option<Vec<ColumnMetadata>>,
+    /// Shorthand for `column_metadata`: one type name per column (ignored when metadata is sent)
+    #[serde(default)]
+    pub column_types: Option<Vec<String>>,
+    /// Whether the file is returned `inline` or as an `attachment` (default)
+    #[serde(default)]
+    #[schemars(schema_with = "disposition_schema")]
+    pub disposition: Option<String>,
+    /// Download filename, sanitized and given the format's extension if missing;
+    /// defaults to the configured template
+    #[serde(default)]
+    pub filename: Option<String>,
+    /// Optional totals row appended after the data rows
+    #[serde(default)]
+    pub summary_row: Option<Vec<String>>,
+    /// Worksheets written instead of the top-level table, whose `headers` and `rows`
+    /// are then ignored (Excel only)
+    #[serde(default)]
+    pub sheets: Option<Vec<Sheet>>,
+    /// Upload the file here instead of returning it
+    #[serde(default)]
+    pub destination: Option<Destination>,
+}
+
+/// `format` is a free string on the wire; advertise the accepted names
+fn format_schema(_: &mut SchemaGenerator) -> Schema {
+    SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        enum_values: Some(
+            ExportFormat::ALL
+                .iter()
+                .map(|format| format.name().into())
+                .collect(),
+        ),
+        ..Default::default()
+    }
+    .into()
+}
+
+/// `disposition` is a free string on the wire; advertise the accepted names
+fn disposition_schema(_: &mut SchemaGenerator) -> Schema {
+    SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        enum_values: Some(
+            Disposition::ALL
+                .iter()
+                .map(|disposition| disposition.as_str().into())
+                .collect(),
+        ),
+        ..Default::default()
+    }
+    .into()
 }
 
 impl ExportRequest {
+    /// Requested `Content-Disposition` type, attachment when none is sent
+    pub fn disposition(&self) -> Result<Disposition, String> {
+        match &self.disposition {
+            None => Ok(Disposition::default()),
+            Some(name) => Disposition::from_name(name)
+                .ok_or_else(|| format!("Invalid disposition: {}", name)),
+        }
+    }
+
     /// Convert to domain model
     pub fn to_domain(&self) -> Result<ExportData, String> {
-        let format = match self.format.to_lowercase().as_str() {
-            "excel" => ExportFormat::Excel,
-            "csv" => ExportFormat::Csv,
-            "pdf" => ExportFormat::Pdf,
-            _ => return Err(format!("Invalid format: {}", self.format)),
+        self.disposition()?;
+        let format = ExportFormat::from_name(&self.format)
+            .ok_or_else(|| format!("Invalid format: {}", self.format))?;
+        let column_metadata = match (&self.column_metadata, &self.column_types) {
+            (Some(metadata), _) => Some(metadata.clone()),
+            (None, Some(types)) => Some(
+                types
+                    .iter()
+                    .map(|name| {
+                        ColumnType::from_name(name)
+                            .map(|column_type| ColumnMetadata { column_type, ..Default::default() })
+                            .ok_or_else(|| format!("Invalid column type: {}", name))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            (None, None) => None,
+        };
+        let headers = if self.has_header.unwrap_or(true) {
+            self.headers.clone()
+        } else if self.headers.is_empty() {
+            let width = self.rows.iter().map(Vec::len).max().unwrap_or(0);
+            (1..=width).map(|n| format!("Column {}", n)).collect()
+        } else {
+            return Err("headers must be empty when has_header is false".to_string());
         };
 
         Ok(ExportData {
             title: self.title.clone(),
             format,
-            headers: self.headers.clone(),
+            headers,
             rows: self.rows.clone(),
             options: self.options.clone(),
-            column_metadata: self.column_metadata.clone(),
+            column_metadata,
+            summary_row: self.summary_row.clone(),
+            sheets: self.sheets.clone(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(column_types: &str) -> ExportRequest {
+        serde_json::from_str(&format!(
+            r#"{{"title": "T", "format": "csv", "headers": ["Qty", "Name"],
+                "rows": [["1", "a"]], "column_types": {}}}"#,
+            column_types
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_column_types_build_column_metadata() {
+        let data = request(r#"["number", "Text"]"#).to_domain().unwrap();
+
+        let types: Vec<ColumnType> = data
+            .column_metadata
+            .unwrap()
+            .iter()
+            .map(|meta| meta.column_type)
+            .collect();
+        assert_eq!(types, vec![ColumnType::Number, ColumnType::Text]);
+    }
+
+    #[test]
+    fn test_invalid_column_type_is_rejected() {
+        let err = request(r#"["number", "money"]"#).to_domain().unwrap_err();
+        assert_eq!(err, "Invalid column type: money");
+    }
+
+    #[test]
+    fn test_column_metadata_takes_precedence_over_column_types() {
+        let mut req = request(r#"["number", "text"]"#);
+        req.column_metadata = Some(vec![ColumnMetadata::currency(), ColumnMetadata::text()]);
+
+        let metadata = req.to_domain().unwrap().column_metadata.unwrap();
+        assert_eq!(metadata[0].column_type, ColumnType::Currency);
+    }
+
+    #[test]
+    fn test_headerless_rows_get_generated_headers() {
+        let req: ExportRequest = serde_json::from_str(
+            r#"{"title": "T", "format": "csv", "headers": [], "has_header": false,
+                "rows": [["Ann", "30"], ["Bob", "41"], ["Cy", "25"]]}"#,
+        )
+        .unwrap();
+
+        let data = req.to_domain().unwrap();
+        assert_eq!(data.headers, vec!["Column 1", "Column 2"]);
+        assert_eq!(data.rows.len(), 3);
+        assert_eq!(data.rows[0], vec!["Ann", "30"]);
+    }
+
+    #[test]
+    fn test_headers_with_has_header_false_are_rejected() {
+        let mut req = request(r#"["number", "text"]"#);
+        req.has_header = Some(false);
+
+        assert!(req.to_domain().unwrap_err().contains("has_header"));
+    }
+
+    #[test]
+    fn test_sheets_are_carried_into_the_domain() {
+        let req: ExportRequest = serde_json::from_str(
+            r#"{"title": "Book", "format": "excel", "headers": [], "rows": [],
+                "sheets": [
+                    {"title": "Orders", "headers": ["Order"], "rows": [["A-1"]]},
+                    {"title": "Customers", "headers": ["Name"], "rows": [["Ann"]],
+                     "column_metadata": [{"column_type": "text"}]}
+                ]}"#,
+        )
+        .unwrap();
+
+        let sheets = req.to_domain().unwrap().sheets.unwrap();
+        let titles: Vec<&str> = sheets.iter().map(|sheet| sheet.title.as_str()).collect();
+        assert_eq!(titles, vec!["Orders", "Customers"]);
+        assert_eq!(sheets[1].rows, vec![vec!["Ann"]]);
+        assert!(sheets[0].column_metadata.is_none());
+    }
+}
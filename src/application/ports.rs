@@ -1,6 +1,41 @@
-use crate::domain::models::ExportData;
+use std::io::Write;
+use std::time::Duration;
+use axum::http::HeaderMap;
+use crate::domain::errors::DomainError;
+use crate::domain::models::{ExportData, ExportFormat};
 
 /// Export service trait (interface)
 pub trait ExportService: Send + Sync {
     fn export(&self, data: &ExportData) -> Result<Vec<u8>, Box<dyn std::error::Error>>;
 }
+
+/// Export service that can write its output incrementally into a sink
+pub trait StreamingExportService: ExportService {
+    fn export_to(
+        &self,
+        data: &ExportData,
+        writer: &mut dyn Write,
+    ) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+/// Sink for the outcome of each export, e.g. operational metrics
+pub trait ExportRecorder: Send + Sync {
+    /// Record one export attempt, `duration` covering validation through generation
+    fn record(&self, format: ExportFormat, duration: Duration, succeeded: bool);
+
+    /// Note a lossy fix the validator made instead of rejecting the export
+    fn repaired(&self, _format: ExportFormat, _warning: &str) {}
+}
+
+/// Authenticated caller, made available to handlers as a request extension
+#[derive(Debug, Clone, PartialEq)]
+pub struct Principal {
+    /// Identifies the caller for rate limiting and filenames
+    pub subject: String,
+}
+
+/// Resolves the caller of a request from its headers
+pub trait Authenticator: Send + Sync {
+    /// `MissingCredentials` when the request carries none, `InvalidToken` when they are wrong
+    fn authenticate(&self, headers: &HeaderMap) -> Result<Principal, DomainError>;
+}
@@ -1,8 +1,71 @@
 use std::sync::Arc;
-use crate::domain::models::{ExportData, ExportFormat};
+use std::time::{Duration, Instant};
+use crate::domain::models::{ExportData, ExportFormat, Sheet};
 use crate::domain::validators::ExportValidator;
 use crate::domain::errors::DomainError;
-use super::ports::ExportService;
+use crate::domain::dedupe::dedupe_sheets;
+use crate::domain::filtering::apply_filter;
+use crate::domain::inference::infer_column_types;
+use crate::domain::sampling::apply_sample;
+use crate::domain::sizing::estimate_output_bytes;
+use crate::domain::sorting::apply_sort;
+use crate::domain::summary::apply_summary_stats;
+use crate::domain::trimming::trim_cells;
+use super::ports::{ExportRecorder, ExportService, StreamingExportService};
+
+/// Prepared data together with the exporter that will stream it and what preparing did
+pub type StreamingExport = (ExportData, Arc<dyn StreamingExportService>, ExportReport);
+
+/// Per-cell transform applied before export, given the column index and cell value
+pub type CellTransformer = Arc<dyn Fn(usize, &str) -> String + Send + Sync>;
+
+/// Time spent in each phase of an export
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExportTimings {
+    /// Validation and preprocessing
+    pub validation: Duration,
+    /// File generation by the exporter
+    pub generation: Duration,
+}
+
+/// What an export did besides producing the file
+#[derive(Debug, Clone, Default)]
+pub struct ExportReport {
+    pub timings: ExportTimings,
+    /// Titles of the sheets `dedupe_sheets` dropped, in request order
+    pub omitted_sheets: Vec<String>,
+    /// Lossy fixes the validator made instead of rejecting the export
+    pub warnings: Vec<String>,
+}
+
+/// Streaming exporter that reports the outcome to a recorder once the stream ends
+struct RecordedStream {
+    inner: Arc<dyn StreamingExportService>,
+    recorder: Arc<dyn ExportRecorder>,
+    started: Instant,
+}
+
+impl ExportService for RecordedStream {
+    fn export(&self, data: &ExportData) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let result = self.inner.export(data);
+        self.recorder
+            .record(data.format, self.started.elapsed(), result.is_ok());
+        result
+    }
+}
+
+impl StreamingExportService for RecordedStream {
+    fn export_to(
+        &self,
+        data: &ExportData,
+        writer: &mut dyn std::io::Write,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let result = self.inner.export_to(data, writer);
+        self.recorder
+            .record(data.format, self.started.elapsed(), result.is_ok());
+        result
+    }
+}
 
 /// Main export use case
 pub struct ExportUseCase {
@@ -10,6 +73,12 @@ pub struct ExportUseCase {
     excel_service: Arc<dyn ExportService>,
     csv_service: Arc<dyn ExportService>,
     pdf_service: Arc<dyn ExportService>,
+    json_service: Arc<dyn ExportService>,
+    html_service: Arc<dyn ExportService>,
+    csv_streaming: Option<Arc<dyn StreamingExportService>>,
+    cell_transformer: Option<CellTransformer>,
+    max_output_bytes: Option<usize>,
+    recorder: Option<Arc<dyn ExportRecorder>>,
 }
 
 impl ExportUseCase {
@@ -18,30 +87,530 @@ impl ExportUseCase {
         excel_service: Arc<dyn ExportService>,
         csv_service: Arc<dyn ExportService>,
         pdf_service: Arc<dyn ExportService>,
+        json_service: Arc<dyn ExportService>,
+        html_service: Arc<dyn ExportService>,
     ) -> Self {
         Self {
             validator,
             excel_service,
             csv_service,
             pdf_service,
+            json_service,
+            html_service,
+            csv_streaming: None,
+            cell_transformer: None,
+            max_output_bytes: None,
+            recorder: None,
         }
     }
 
+    /// Stream CSV and TSV exports through this service instead of buffering them
+    pub fn with_csv_streaming(mut self, service: Arc<dyn StreamingExportService>) -> Self {
+        self.csv_streaming = Some(service);
+        self
+    }
+
+    /// Transform every data and summary cell (e.g. to mask PII) before export
+    ///
+    /// The transformer runs last, after validation, sampling, type inference and
+    /// sorting, so those steps see the original values and its output is not
+    /// re-validated.
+    pub fn with_cell_transformer(mut self, transformer: CellTransformer) -> Self {
+        self.cell_transformer = Some(transformer);
+        self
+    }
+
+    /// Reject exports whose estimated output exceeds `max` bytes before generating them
+    pub fn with_max_output_bytes(mut self, max: usize) -> Self {
+        self.max_output_bytes = Some(max);
+        self
+    }
+
+    /// Report every export's format, duration and outcome to `recorder`
+    ///
+    /// Streamed exports are recorded when the stream finishes.
+    pub fn with_recorder(mut self, recorder: Arc<dyn ExportRecorder>) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    fn record(&self, format: ExportFormat, started: Instant, succeeded: bool) {
+        if let Some(recorder) = &self.recorder {
+            recorder.record(format, started.elapsed(), succeeded);
+        }
+    }
+
+    /// Streaming exporter for `format`, if one is configured
+    fn streaming_service(&self, format: ExportFormat) -> Option<Arc<dyn StreamingExportService>> {
+        match format {
+            ExportFormat::Csv | ExportFormat::Tsv => self.csv_streaming.clone(),
+            ExportFormat::Excel | ExportFormat::Pdf | ExportFormat::Json | ExportFormat::Html => {
+                None
+            }
+        }
+    }
+
+    /// Whether `format` can be exported through `prepare_streaming`
+    pub fn streams(&self, format: ExportFormat) -> bool {
+        self.streaming_service(format).is_some()
+    }
+
+    /// Validate and preprocess data, returning it with the streaming exporter for its format
+    ///
+    /// Check `streams` first: formats without a streaming exporter are rejected before any
+    /// work is done and must go through `execute`.
+    pub fn prepare_streaming(&self, data: ExportData) -> Result<StreamingExport, DomainError> {
+        let format = data.format;
+        let service = self.streaming_service(format).ok_or_else(|| {
+            DomainError::InvalidFormat(format!("{} exports cannot be streamed", format.name()))
+        })?;
+        let started = Instant::now();
+        let (data, report) =
+            self.prepare(data).inspect_err(|_| self.record(format, started, false))?;
+        let service = match &self.recorder {
+            Some(recorder) => Arc::new(RecordedStream {
+                inner: service,
+                recorder: recorder.clone(),
+                started,
+            }),
+            None => service,
+        };
+        Ok((data, service, report))
+    }
+
+    /// Check data against the validator without exporting it
+    pub fn validate(&self, data: &ExportData) -> Result<(), DomainError> {
+        let mut data = data.clone();
+        self.validator.repair(&mut data);
+        self.validator.validate(&data)
+    }
+
+    /// Validate data, drop duplicate sheets if asked to, and apply preprocessing (filtering,
+    /// sampling, trimming, type inference, summary statistics, sorting, then the cell
+    /// transformer) to the table or to each sheet, then check the estimated output size
+    ///
+    /// Returns the prepared data with a report of the repairs made and the sheets deduping
+    /// dropped; its timings are left for the caller.
+    fn prepare(&self, mut data: ExportData) -> Result<(ExportData, ExportReport), DomainError> {
+        let warnings = self.validator.repair(&mut data);
+        for warning in &warnings {
+            eprintln!("Warning: {} export: {}", data.format.name(), warning);
+            if let Some(recorder) = &self.recorder {
+                recorder.repaired(data.format, warning);
+            }
+        }
+        self.validator.validate(&data)?;
+        let omitted_sheets = dedupe_sheets(&mut data);
+        match data.sheets.take() {
+            Some(sheets) => {
+                let sheets = sheets
+                    .into_iter()
+                    .map(|sheet| Sheet::from(self.preprocess(data.for_sheet(sheet))))
+                    .collect();
+                data.sheets = Some(sheets);
+            }
+            None => data = self.preprocess(data),
+        }
+        if let Some(max) = self.max_output_bytes {
+            let estimated = estimate_output_bytes(&data);
+            if estimated > max {
+                return Err(DomainError::OutputTooLarge { estimated, max });
+            }
+        }
+        let report = ExportReport {
+            omitted_sheets,
+            warnings,
+            ..ExportReport::default()
+        };
+        Ok((data, report))
+    }
+
+    /// Apply the preprocessing steps to one validated table
+    fn preprocess(&self, mut data: ExportData) -> ExportData {
+        apply_filter(&mut data);
+        apply_sample(&mut data);
+        trim_cells(&mut data);
+        infer_column_types(&mut data);
+        apply_summary_stats(&mut data);
+        apply_sort(&mut data);
+        if let Some(transform) = &self.cell_transformer {
+            let summary = data.summary_row.iter_mut();
+            for row in data.rows.iter_mut().chain(summary) {
+                for (col_idx, cell) in row.iter_mut().enumerate() {
+                    *cell = transform(col_idx, cell);
+                }
+            }
+        }
+        data
+    }
+
     /// Execute export
     pub fn execute(&self, data: ExportData) -> Result<Vec<u8>, DomainError> {
-        // Step 1: Validate data
-        self.validator.validate(&data)?;
+        self.execute_reported(data).map(|(bytes, _)| bytes)
+    }
+
+    /// Execute export, also reporting how long each phase took, the repairs made and which
+    /// sheets were dropped
+    pub fn execute_reported(
+        &self,
+        data: ExportData,
+    ) -> Result<(Vec<u8>, ExportReport), DomainError> {
+        let format = data.format;
+        let started = Instant::now();
+        let result = self.export_reported(data);
+        self.record(format, started, result.is_ok());
+        result
+    }
+
+    fn export_reported(&self, data: ExportData) -> Result<(Vec<u8>, ExportReport), DomainError> {
+        // Step 1: Validate and preprocess data
+        let started = Instant::now();
+        let (data, mut report) = self.prepare(data)?;
+        let validation = started.elapsed();
 
         // Step 2: Select appropriate service
         let service = match data.format {
             ExportFormat::Excel => self.excel_service.clone(),
-            ExportFormat::Csv => self.csv_service.clone(),
+            ExportFormat::Csv | ExportFormat::Tsv => self.csv_service.clone(),
             ExportFormat::Pdf => self.pdf_service.clone(),
+            ExportFormat::Json => self.json_service.clone(),
+            ExportFormat::Html => self.html_service.clone(),
         };
 
         // Step 3: Export and return binary data
-        service
+        let started = Instant::now();
+        let bytes = service
             .export(&data)
-            .map_err(|e| DomainError::InvalidFormat(e.to_string()))
+            .map_err(|e| DomainError::InvalidFormat(e.to_string()))?;
+        report.timings = ExportTimings {
+            validation,
+            generation: started.elapsed(),
+        };
+        Ok((bytes, report))
+    }
+}
+
+#[cfg(all(test, feature = "csv"))]
+mod tests {
+    use super::*;
+    use crate::domain::validators::{DefaultExportValidator, OverlongCellMode, ValidationLimits};
+    use crate::infrastructure::exporters::CsvExporter;
+
+    fn csv_use_case() -> ExportUseCase {
+        let csv: Arc<dyn ExportService> = Arc::new(CsvExporter);
+        ExportUseCase::new(
+            Arc::new(DefaultExportValidator::default()),
+            csv.clone(),
+            csv.clone(),
+            csv.clone(),
+            csv.clone(),
+            csv,
+        )
+    }
+
+    #[test]
+    fn test_cell_transformer_masks_column() {
+        let mask_email: CellTransformer = Arc::new(|col_idx, cell| match col_idx {
+            1 => "*".repeat(cell.chars().count()),
+            _ => cell.to_string(),
+        });
+        let data = ExportData {
+            title: "Users".to_string(),
+            format: ExportFormat::Csv,
+            headers: vec!["Name".to_string(), "Email".to_string()],
+            rows: vec![vec!["Ann".to_string(), "a@b.io".to_string()]],
+            options: None,
+            column_metadata: None,
+            summary_row: None,
+            sheets: None,
+        };
+
+        let plain = csv_use_case().execute(data.clone()).unwrap();
+        let masked = csv_use_case()
+            .with_cell_transformer(mask_email)
+            .execute(data)
+            .unwrap();
+
+        assert_eq!(String::from_utf8(plain).unwrap(), "Name,Email\nAnn,a@b.io\n");
+        assert_eq!(String::from_utf8(masked).unwrap(), "Name,Email\nAnn,******\n");
+    }
+
+    #[test]
+    fn test_trim_cells_keeps_preserved_columns() {
+        use crate::domain::models::{ColumnMetadata, ExportOptions};
+
+        let data = ExportData {
+            title: "Codes".to_string(),
+            format: ExportFormat::Csv,
+            headers: vec!["Name".to_string(), "Code".to_string()],
+            rows: vec![vec!["  Ann ".to_string(), "  A1 ".to_string()]],
+            options: Some(ExportOptions {
+                trim_cells: Some(true),
+                ..Default::default()
+            }),
+            column_metadata: Some(vec![
+                ColumnMetadata::text(),
+                ColumnMetadata::text().with_preserve_whitespace(),
+            ]),
+            summary_row: None,
+            sheets: None,
+        };
+
+        let bytes = csv_use_case().execute(data).unwrap();
+        assert_eq!(String::from_utf8(bytes).unwrap(), "Name,Code\nAnn,  A1 \n");
+    }
+
+    #[test]
+    fn test_summary_stats_append_column_sums() {
+        use crate::domain::models::{ColumnMetadata, ExportOptions, SummaryStat};
+
+        let data = ExportData {
+            title: "Sales".to_string(),
+            format: ExportFormat::Csv,
+            headers: vec!["Item".to_string(), "Price".to_string()],
+            rows: vec![
+                vec!["Widget".to_string(), "5.00".to_string()],
+                vec!["Gadget".to_string(), "150.00".to_string()],
+            ],
+            options: Some(ExportOptions {
+                summary_stats: Some(SummaryStat::Sum),
+                ..Default::default()
+            }),
+            column_metadata: Some(vec![ColumnMetadata::text(), ColumnMetadata::currency()]),
+            summary_row: None,
+            sheets: None,
+        };
+
+        let csv = String::from_utf8(csv_use_case().execute(data).unwrap()).unwrap();
+        assert_eq!(csv.lines().last(), Some(",155.00"));
+    }
+
+    /// Default validator that counts how often data is validated
+    #[derive(Default)]
+    struct CountingValidator {
+        inner: DefaultExportValidator,
+        validations: std::sync::atomic::AtomicUsize,
+    }
+
+    impl ExportValidator for CountingValidator {
+        fn validate(&self, data: &ExportData) -> Result<(), DomainError> {
+            self.validations
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            self.inner.validate(data)
+        }
+    }
+
+    #[derive(Default)]
+    struct Outcomes(std::sync::Mutex<Vec<(ExportFormat, bool)>>);
+
+    impl ExportRecorder for Outcomes {
+        fn record(&self, format: ExportFormat, _duration: Duration, succeeded: bool) {
+            self.0.lock().unwrap().push((format, succeeded));
+        }
+    }
+
+    #[test]
+    fn test_each_export_is_validated_once_and_recorded() {
+        let validator = Arc::new(CountingValidator::default());
+        let outcomes = Arc::new(Outcomes::default());
+        let csv: Arc<dyn ExportService> = Arc::new(CsvExporter);
+        let use_case = ExportUseCase::new(
+            validator.clone(),
+            csv.clone(),
+            csv.clone(),
+            csv.clone(),
+            csv.clone(),
+            csv,
+        )
+        .with_csv_streaming(Arc::new(CsvExporter))
+        .with_recorder(outcomes.clone());
+        let data = |format, rows: Vec<Vec<String>>| ExportData {
+            title: "Report".to_string(),
+            format,
+            headers: vec!["Name".to_string()],
+            rows,
+            options: None,
+            column_metadata: None,
+            summary_row: None,
+            sheets: None,
+        };
+        let rows = || vec![vec!["Widget".to_string()]];
+        let validations = || validator.validations.load(std::sync::atomic::Ordering::Relaxed);
+
+        assert!(!use_case.streams(ExportFormat::Pdf));
+        assert!(use_case.execute(data(ExportFormat::Pdf, rows())).is_ok());
+        assert_eq!(validations(), 1);
+        assert!(use_case.execute(data(ExportFormat::Pdf, Vec::new())).is_err());
+        assert_eq!(validations(), 2);
+
+        assert!(use_case.streams(ExportFormat::Csv));
+        assert!(use_case.prepare_streaming(data(ExportFormat::Csv, Vec::new())).is_err());
+        assert_eq!(validations(), 3);
+
+        let outcomes = outcomes.0.lock().unwrap().clone();
+        assert_eq!(
+            outcomes,
+            vec![(ExportFormat::Pdf, true), (ExportFormat::Pdf, false), (ExportFormat::Csv, false)]
+        );
+    }
+
+    #[derive(Default)]
+    struct Repairs(std::sync::Mutex<Vec<(ExportFormat, String)>>);
+
+    impl ExportRecorder for Repairs {
+        fn record(&self, _format: ExportFormat, _duration: Duration, _succeeded: bool) {}
+
+        fn repaired(&self, format: ExportFormat, warning: &str) {
+            self.0.lock().unwrap().push((format, warning.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_repair_warnings_reach_the_recorder() {
+        let repairs = Arc::new(Repairs::default());
+        let validator = DefaultExportValidator::new(ValidationLimits {
+            max_cell_length: 4,
+            overlong_cells: OverlongCellMode::Truncate,
+            ..Default::default()
+        });
+        let csv: Arc<dyn ExportService> = Arc::new(CsvExporter);
+        let use_case = ExportUseCase::new(
+            Arc::new(validator),
+            csv.clone(),
+            csv.clone(),
+            csv.clone(),
+            csv.clone(),
+            csv,
+        )
+        .with_recorder(repairs.clone());
+        let data = ExportData {
+            title: "Report".to_string(),
+            format: ExportFormat::Csv,
+            headers: vec!["Name".to_string()],
+            rows: vec![vec!["Widget".to_string()]],
+            options: None,
+            column_metadata: None,
+            summary_row: Some(vec!["Total".to_string()]),
+            sheets: None,
+        };
+
+        assert_eq!(use_case.execute(data).unwrap(), b"Name\nWidg\nTota\n");
+        let repairs = repairs.0.lock().unwrap().clone();
+        assert_eq!(
+            repairs,
+            vec![
+                (ExportFormat::Csv, "truncated 6-byte cell in row 1 to 4 bytes".to_string()),
+                (ExportFormat::Csv, "truncated 5-byte cell in summary row to 4 bytes".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sheets_are_deduped_after_repair() {
+        let validator = DefaultExportValidator::new(ValidationLimits {
+            max_cell_length: 4,
+            overlong_cells: OverlongCellMode::Truncate,
+            ..Default::default()
+        });
+        let csv: Arc<dyn ExportService> = Arc::new(CsvExporter);
+        let use_case = ExportUseCase::new(
+            Arc::new(validator),
+            csv.clone(),
+            csv.clone(),
+            csv.clone(),
+            csv.clone(),
+            csv,
+        );
+        let sheet = |title: &str, value: &str| Sheet {
+            title: title.to_string(),
+            headers: vec!["Name".to_string()],
+            rows: vec![vec![value.to_string()]],
+            column_metadata: None,
+            summary_row: None,
+        };
+        let data = ExportData {
+            title: "Workbook".to_string(),
+            format: ExportFormat::Excel,
+            headers: Vec::new(),
+            rows: Vec::new(),
+            options: Some(crate::domain::models::ExportOptions {
+                dedupe_sheets: Some(true),
+                ..Default::default()
+            }),
+            column_metadata: None,
+            summary_row: None,
+            sheets: Some(vec![sheet("Q1", "Widget"), sheet("Q2", "Widgets")]),
+        };
+
+        // The cells differ as sent but are the same once truncated
+        let (_, report) = use_case.execute_reported(data).unwrap();
+        assert_eq!(report.omitted_sheets, vec!["Q2"]);
+    }
+
+    /// Writes the column type of every header instead of the data
+    struct ColumnTypesExporter;
+
+    impl ExportService for ColumnTypesExporter {
+        fn export(&self, data: &ExportData) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+            let types: Vec<String> = (0..data.headers.len())
+                .map(|col_idx| format!("{:?}", data.column_type(col_idx)))
+                .collect();
+            Ok(types.join(",").into_bytes())
+        }
+    }
+
+    #[test]
+    fn test_short_metadata_leaves_missing_columns_to_inference() {
+        use crate::domain::models::{ColumnMetadata, ExportOptions};
+
+        let types: Arc<dyn ExportService> = Arc::new(ColumnTypesExporter);
+        let use_case = ExportUseCase::new(
+            Arc::new(DefaultExportValidator::default()),
+            types.clone(),
+            types.clone(),
+            types.clone(),
+            types.clone(),
+            types,
+        );
+        let data = |infer_types| ExportData {
+            title: "Orders".to_string(),
+            format: ExportFormat::Csv,
+            headers: vec!["Item".to_string(), "Shipped".to_string(), "Qty".to_string()],
+            rows: vec![vec!["Widget".to_string(), "2024-03-01".to_string(), "2".to_string()]],
+            options: Some(ExportOptions {
+                infer_types,
+                ..Default::default()
+            }),
+            column_metadata: Some(vec![ColumnMetadata::text()]),
+            summary_row: None,
+            sheets: None,
+        };
+
+        let inferred = use_case.execute(data(Some(true))).unwrap();
+        let padded = use_case.execute(data(None)).unwrap();
+
+        assert_eq!(String::from_utf8(inferred).unwrap(), "Text,Date,Number");
+        assert_eq!(String::from_utf8(padded).unwrap(), "Text,Text,Text");
+    }
+
+    #[test]
+    fn test_estimated_output_over_cap_is_rejected() {
+        let data = |rows: usize| ExportData {
+            title: "Bulk".to_string(),
+            format: ExportFormat::Csv,
+            headers: vec!["Name".to_string(), "Notes".to_string()],
+            rows: vec![vec!["Widget".to_string(), "x".repeat(100)]; rows],
+            options: None,
+            column_metadata: None,
+            summary_row: None,
+            sheets: None,
+        };
+        let use_case = csv_use_case().with_max_output_bytes(10 * 1024);
+
+        assert!(use_case.execute(data(10)).is_ok());
+        assert!(matches!(
+            use_case.execute(data(1000)),
+            Err(DomainError::OutputTooLarge { max: 10240, .. })
+        ));
     }
 }
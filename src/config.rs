@@ -0,0 +1,302 @@
+use std::cell::RefCell;
+use std::env;
+use crate::domain::validators::ValidationLimits;
+use crate::infrastructure::auth::AuthScheme;
+
+/// Service configuration, read from environment variables at startup
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    pub host: String,
+    pub port: u16,
+    pub jwt_secret: String,
+    pub jwt_expiration_seconds: i64,
+    /// `aud` claim issued in tokens and required when validating them
+    pub jwt_audience: Option<String>,
+    /// `iss` claim issued in tokens and required when validating them
+    pub jwt_issuer: Option<String>,
+    /// How export requests authenticate
+    pub auth_scheme: AuthScheme,
+    /// Static key accepted in `X-API-Key` when `auth_scheme` is `ApiKey`
+    pub api_key: String,
+    /// Limits enforced by the export validator
+    pub validation: ValidationLimits,
+    /// Maximum accepted request body size in bytes
+    pub max_request_bytes: usize,
+    /// Maximum estimated export size in bytes; larger exports are rejected before generation
+    pub max_output_bytes: usize,
+    /// Excel number format codes for numeric column types
+    pub excel_formats: ExcelNumberFormats,
+    /// Symbol put before PDF currency amounts when the request has no `locale`
+    pub pdf_currency_symbol: String,
+    /// Text drawn at the bottom of every PDF page, e.g. a confidentiality notice
+    pub pdf_footer_text: Option<String>,
+    /// Export requests allowed per token subject in each rate limit window
+    pub rate_limit_requests: u32,
+    /// Length of the rate limit window in seconds
+    pub rate_limit_window_seconds: u64,
+    /// Host patterns (`host` or `*.domain`) exports may be uploaded to; empty disables uploads
+    pub upload_allowed_hosts: Vec<String>,
+    /// Client ids tokens may be issued for, each becoming the token subject (and so the
+    /// rate limit and job owner); empty issues every token for the shared default subject
+    pub token_client_ids: Vec<String>,
+    /// CSV downloads larger than this many bytes are sent as a single-entry ZIP;
+    /// `None` always sends plain (streamed) CSV
+    pub csv_zip_threshold_bytes: Option<usize>,
+    /// Download filename template (`{title}`, `{timestamp}`, `{date}`, `{format}`, `{sub}`);
+    /// the format's extension is appended
+    pub filename_template: String,
+    /// Seconds a finished asynchronous export job is kept for download
+    pub job_ttl_seconds: u64,
+    /// Seconds an asynchronous export job may stay pending before it fails as timed out
+    pub job_timeout_seconds: u64,
+    /// Most asynchronous export jobs held at once; further submissions are refused
+    pub max_jobs: usize,
+    /// Environment variables that were set but could not be parsed; `validate` reports them
+    pub env_errors: Vec<String>,
+}
+
+/// Excel number format codes applied per numeric `ColumnType`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExcelNumberFormats {
+    pub number: String,
+    pub currency: String,
+    pub percentage: String,
+    /// Applied to `date` cells, which Excel stores as date serials
+    pub date: String,
+}
+
+impl Default for ExcelNumberFormats {
+    fn default() -> Self {
+        Self {
+            number: "#,##0.00".to_string(),
+            currency: "#,##0.00".to_string(),
+            // Percentage cells are written as fractions, so Excel scales them by 100
+            percentage: "0.0%".to_string(),
+            date: "yyyy-mm-dd".to_string(),
+        }
+    }
+}
+
+impl ExcelNumberFormats {
+    /// Check that every format code is non-empty
+    pub fn validate(&self) -> Result<(), String> {
+        let codes = [
+            ("number", &self.number),
+            ("currency", &self.currency),
+            ("percentage", &self.percentage),
+            ("date", &self.date),
+        ];
+        match codes.iter().find(|(_, code)| code.trim().is_empty()) {
+            Some((name, _)) => Err(format!("Excel {} format code cannot be empty", name)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1".to_string(),
+            port: 3000,
+            jwt_secret: "dev-secret-key".to_string(),
+            jwt_expiration_seconds: 3600,
+            jwt_audience: None,
+            jwt_issuer: None,
+            auth_scheme: AuthScheme::Jwt,
+            api_key: String::new(),
+            validation: ValidationLimits::default(),
+            max_request_bytes: 2 * 1024 * 1024,
+            max_output_bytes: 64 * 1024 * 1024,
+            excel_formats: ExcelNumberFormats::default(),
+            pdf_currency_symbol: String::new(),
+            pdf_footer_text: None,
+            rate_limit_requests: 60,
+            rate_limit_window_seconds: 60,
+            upload_allowed_hosts: Vec::new(),
+            token_client_ids: Vec::new(),
+            csv_zip_threshold_bytes: None,
+            filename_template: "{title}_{timestamp}".to_string(),
+            job_ttl_seconds: 600,
+            job_timeout_seconds: 300,
+            max_jobs: 100,
+            env_errors: Vec::new(),
+        }
+    }
+}
+
+impl AppConfig {
+    /// Build configuration from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let env = EnvParser::default();
+        let config = Self {
+            host: env::var("SERVER_HOST").unwrap_or(defaults.host),
+            port: env.parse("SERVER_PORT").unwrap_or(defaults.port),
+            jwt_secret: env::var("JWT_SECRET").unwrap_or(defaults.jwt_secret),
+            jwt_expiration_seconds: env.parse("JWT_EXPIRATION_SECONDS")
+                .unwrap_or(defaults.jwt_expiration_seconds),
+            jwt_audience: env::var("JWT_AUDIENCE").ok().or(defaults.jwt_audience),
+            jwt_issuer: env::var("JWT_ISSUER").ok().or(defaults.jwt_issuer),
+            auth_scheme: env.parse("AUTH_SCHEME").unwrap_or(defaults.auth_scheme),
+            api_key: env::var("API_KEY").unwrap_or(defaults.api_key),
+            validation: ValidationLimits {
+                max_rows: env.parse("MAX_EXPORT_ROWS").unwrap_or(defaults.validation.max_rows),
+                max_columns: env.parse("MAX_COLUMNS").unwrap_or(defaults.validation.max_columns),
+                max_cell_length: env.parse("MAX_CELL_LENGTH")
+                    .unwrap_or(defaults.validation.max_cell_length),
+                max_header_length: env.parse("MAX_HEADER_LENGTH")
+                    .unwrap_or(defaults.validation.max_header_length),
+                overlong_cells: env.parse("OVERLONG_CELLS")
+                    .unwrap_or(defaults.validation.overlong_cells),
+                allow_empty_rows: env.parse("ALLOW_EMPTY_ROWS")
+                    .unwrap_or(defaults.validation.allow_empty_rows),
+                short_metadata: env.parse("SHORT_METADATA")
+                    .unwrap_or(defaults.validation.short_metadata),
+                jagged_rows: env.parse("JAGGED_ROWS").unwrap_or(defaults.validation.jagged_rows),
+            },
+            max_request_bytes: env.parse("MAX_REQUEST_BYTES").unwrap_or(defaults.max_request_bytes),
+            max_output_bytes: env.parse("MAX_OUTPUT_BYTES").unwrap_or(defaults.max_output_bytes),
+            excel_formats: ExcelNumberFormats {
+                number: env::var("EXCEL_NUMBER_FORMAT").unwrap_or(defaults.excel_formats.number),
+                currency: env::var("EXCEL_CURRENCY_FORMAT")
+                    .unwrap_or(defaults.excel_formats.currency),
+                percentage: env::var("EXCEL_PERCENTAGE_FORMAT")
+                    .unwrap_or(defaults.excel_formats.percentage),
+                date: env::var("EXCEL_DATE_FORMAT").unwrap_or(defaults.excel_formats.date),
+            },
+            pdf_currency_symbol: env::var("PDF_CURRENCY_SYMBOL")
+                .unwrap_or(defaults.pdf_currency_symbol),
+            pdf_footer_text: env::var("PDF_FOOTER_TEXT")
+                .ok()
+                .filter(|text| !text.trim().is_empty())
+                .or(defaults.pdf_footer_text),
+            rate_limit_requests: env.parse("RATE_LIMIT_REQUESTS")
+                .unwrap_or(defaults.rate_limit_requests),
+            rate_limit_window_seconds: env.parse("RATE_LIMIT_WINDOW_SECONDS")
+                .unwrap_or(defaults.rate_limit_window_seconds),
+            upload_allowed_hosts: env::var("UPLOAD_ALLOWED_HOSTS")
+                .map(|hosts| hosts.split(',').map(str::to_string).collect())
+                .unwrap_or(defaults.upload_allowed_hosts),
+            token_client_ids: env::var("TOKEN_CLIENT_IDS")
+                .map(|ids| {
+                    ids.split(',')
+                        .map(|id| id.trim().to_string())
+                        .filter(|id| !id.is_empty())
+                        .collect()
+                })
+                .unwrap_or(defaults.token_client_ids),
+            csv_zip_threshold_bytes: env.parse("CSV_ZIP_THRESHOLD_BYTES")
+                .or(defaults.csv_zip_threshold_bytes),
+            filename_template: env::var("EXPORT_FILENAME_TEMPLATE")
+                .unwrap_or(defaults.filename_template),
+            job_ttl_seconds: env.parse("EXPORT_JOB_TTL_SECONDS")
+                .unwrap_or(defaults.job_ttl_seconds),
+            job_timeout_seconds: env.parse("EXPORT_JOB_TIMEOUT_SECONDS")
+                .unwrap_or(defaults.job_timeout_seconds),
+            max_jobs: env.parse("EXPORT_MAX_JOBS")
+                .unwrap_or(defaults.max_jobs),
+            env_errors: Vec::new(),
+        };
+        Self {
+            env_errors: env.errors.into_inner(),
+            ..config
+        }
+    }
+
+    /// Check values that cannot fall back to a default
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.env_errors.is_empty() {
+            return Err(self.env_errors.join("; "));
+        }
+        if self.rate_limit_requests == 0 || self.rate_limit_window_seconds == 0 {
+            return Err("Rate limit requests and window must be at least 1".to_string());
+        }
+        if self.auth_scheme == AuthScheme::ApiKey && self.api_key.is_empty() {
+            return Err("API_KEY must be set when AUTH_SCHEME is api_key".to_string());
+        }
+        if self.filename_template.trim().is_empty() {
+            return Err("Export filename template cannot be empty".to_string());
+        }
+        if self.job_ttl_seconds == 0 {
+            return Err("Export job TTL must be at least 1 second".to_string());
+        }
+        if self.job_timeout_seconds == 0 {
+            return Err("Export job timeout must be at least 1 second".to_string());
+        }
+        if self.max_jobs == 0 {
+            return Err("Export job limit must be at least 1".to_string());
+        }
+        self.excel_formats.validate()
+    }
+
+    /// Address the server binds to
+    pub fn bind_address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+/// Parses environment variables, collecting the malformed ones for `validate`
+#[derive(Default)]
+struct EnvParser {
+    errors: RefCell<Vec<String>>,
+}
+
+impl EnvParser {
+    /// Parse an environment variable; None when it is missing or malformed
+    fn parse<T>(&self, key: &str) -> Option<T>
+    where
+        T: std::str::FromStr,
+        T::Err: std::fmt::Display,
+    {
+        let value = env::var(key).ok()?;
+        match value.trim().parse() {
+            Ok(parsed) => Some(parsed),
+            Err(e) => {
+                self.errors
+                    .borrow_mut()
+                    .push(format!("{} has an invalid value '{}': {}", key, value, e));
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::validators::OverlongCellMode;
+
+    #[test]
+    fn test_empty_excel_format_code_is_rejected() {
+        let mut config = AppConfig::default();
+        assert!(config.validate().is_ok());
+
+        config.excel_formats.currency = "  ".to_string();
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("currency"), "{}", err);
+    }
+
+    #[test]
+    fn test_malformed_env_value_fails_validation() {
+        std::env::set_var("OVERLONG_CELLS", "trunc");
+        let config = AppConfig::from_env();
+        std::env::remove_var("OVERLONG_CELLS");
+
+        // The default stays in place, but startup is refused
+        assert_eq!(config.validation.overlong_cells, OverlongCellMode::Reject);
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("OVERLONG_CELLS has an invalid value 'trunc'"), "{}", err);
+    }
+
+    #[test]
+    fn test_api_key_scheme_requires_key() {
+        let mut config = AppConfig {
+            auth_scheme: AuthScheme::ApiKey,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        config.api_key = "s3cret-key".to_string();
+        assert!(config.validate().is_ok());
+    }
+}
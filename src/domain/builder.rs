@@ -0,0 +1,135 @@
+use super::errors::DomainError;
+use super::models::{ColumnMetadata, ExportData, ExportFormat, ExportOptions};
+
+/// Fluent construction of `ExportData` for library callers
+///
+/// Everything not set is left empty or `None`; `build` only checks what the data
+/// cannot be exported without. Full validation still happens in the use case.
+#[derive(Debug, Clone, Default)]
+pub struct ExportDataBuilder {
+    title: String,
+    format: Option<ExportFormat>,
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    options: Option<ExportOptions>,
+    column_metadata: Option<Vec<ColumnMetadata>>,
+}
+
+impl ExportDataBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn format(mut self, format: ExportFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    pub fn headers<I, S>(mut self, headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.headers = headers.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Append one row after those already added
+    pub fn add_row<I, S>(mut self, row: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.rows.push(row.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Replace all rows
+    pub fn rows(mut self, rows: Vec<Vec<String>>) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    pub fn options(mut self, options: ExportOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    pub fn column_metadata(mut self, metadata: Vec<ColumnMetadata>) -> Self {
+        self.column_metadata = Some(metadata);
+        self
+    }
+
+    /// Finish the data; fails when no format or no headers were given
+    pub fn build(self) -> Result<ExportData, DomainError> {
+        let format = self
+            .format
+            .ok_or_else(|| DomainError::InvalidFormat("No export format set".to_string()))?;
+        if self.headers.is_empty() {
+            return Err(DomainError::EmptyData("Headers cannot be empty".to_string()));
+        }
+        Ok(ExportData {
+            title: self.title,
+            format,
+            headers: self.headers,
+            rows: self.rows,
+            options: self.options,
+            column_metadata: self.column_metadata,
+            summary_row: None,
+            sheets: None,
+        })
+    }
+}
+
+impl ExportData {
+    /// Start building export data fluently
+    pub fn builder() -> ExportDataBuilder {
+        ExportDataBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::ColumnType;
+
+    #[test]
+    fn test_builder_collects_rows_and_leaves_omitted_fields_empty() {
+        let data = ExportData::builder()
+            .title("Sales")
+            .format(ExportFormat::Csv)
+            .headers(["Item", "Amount"])
+            .add_row(["Widget", "5.00"])
+            .add_row(vec!["Gadget".to_string(), "150.00".to_string()])
+            .column_metadata(vec![ColumnMetadata::text(), ColumnMetadata::currency()])
+            .build()
+            .unwrap();
+
+        assert_eq!(data.title, "Sales");
+        assert_eq!(data.format, ExportFormat::Csv);
+        assert_eq!(data.headers, vec!["Item", "Amount"]);
+        assert_eq!(data.rows, vec![vec!["Widget", "5.00"], vec!["Gadget", "150.00"]]);
+        assert_eq!(data.column_type(1), ColumnType::Currency);
+        assert!(data.options.is_none());
+        assert!(data.summary_row.is_none());
+        assert!(data.sheets.is_none());
+    }
+
+    #[test]
+    fn test_builder_requires_format_and_headers() {
+        let rows = vec![vec!["Widget".to_string()]];
+        let no_headers = ExportData::builder()
+            .format(ExportFormat::Pdf)
+            .rows(rows.clone())
+            .build();
+        let no_format = ExportData::builder().headers(["Name"]).rows(rows).build();
+
+        assert!(matches!(no_headers, Err(DomainError::EmptyData(_))));
+        assert!(matches!(no_format, Err(DomainError::InvalidFormat(_))));
+    }
+}
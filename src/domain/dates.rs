@@ -0,0 +1,135 @@
+use std::fmt::Write;
+use chrono::format::{Item, StrftimeItems};
+use chrono::NaiveDate;
+use super::locale::Locale;
+use super::models::ExportOptions;
+
+/// chrono format of ISO-8601 dates (`YYYY-MM-DD`), the default input and output format
+pub const ISO_DATE: &str = "%Y-%m-%d";
+
+/// Parses `Date` cells in one chrono format and renders them in another
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateFormatter {
+    input: String,
+    output: String,
+}
+
+impl Default for DateFormatter {
+    fn default() -> Self {
+        Self::new(ISO_DATE, ISO_DATE)
+    }
+}
+
+impl DateFormatter {
+    pub fn new(input: impl Into<String>, output: impl Into<String>) -> Self {
+        Self {
+            input: input.into(),
+            output: output.into(),
+        }
+    }
+
+    /// Formatter requested by export options, or `None` when dates are left as sent
+    ///
+    /// Cells are parsed with `date_input_format` (ISO by default) and rendered with
+    /// `date_output_format`, falling back to the `locale`'s date format and then ISO.
+    pub fn from_options(options: Option<&ExportOptions>) -> Option<Self> {
+        let options = options?;
+        let locale_output = options
+            .locale
+            .as_deref()
+            .and_then(Locale::from_tag)
+            .map(|locale| locale.date_format.to_string());
+        let output = options.date_output_format.clone().or(locale_output);
+        if options.date_input_format.is_none() && output.is_none() {
+            return None;
+        }
+        Some(Self::new(
+            options.date_input_format.as_deref().unwrap_or(ISO_DATE),
+            output.unwrap_or_else(|| ISO_DATE.to_string()),
+        ))
+    }
+
+    /// Parse a cell with the input format
+    pub fn parse(&self, cell: &str) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(cell.trim(), &self.input).ok()
+    }
+
+    /// Render a cell in the output format; `None` when it does not parse
+    pub fn format(&self, cell: &str) -> Option<String> {
+        let date = self.parse(cell)?;
+        let mut rendered = String::new();
+        write!(rendered, "{}", date.format(&self.output)).ok()?;
+        Some(rendered)
+    }
+
+    /// Render a cell in the output format, passing values that do not parse through unchanged
+    pub fn format_cell(&self, cell: &str) -> String {
+        self.format(cell).unwrap_or_else(|| cell.to_string())
+    }
+}
+
+/// Whether `format` is a usable chrono format string
+pub fn is_valid_format(format: &str) -> bool {
+    !format.trim().is_empty() && !StrftimeItems::new(format).any(|item| item == Item::Error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_dates_are_reformatted() {
+        let formatter = DateFormatter::new(ISO_DATE, "%d/%m/%Y");
+
+        assert_eq!(formatter.format_cell("2024-01-25"), "25/01/2024");
+        assert_eq!(formatter.format_cell(" 2024-12-31 "), "31/12/2024");
+        assert_eq!(
+            DateFormatter::new("%d.%m.%Y", ISO_DATE).format_cell("25.01.2024"),
+            "2024-01-25"
+        );
+    }
+
+    #[test]
+    fn test_invalid_dates_pass_through() {
+        let formatter = DateFormatter::new(ISO_DATE, "%d/%m/%Y");
+
+        for cell in ["soon", "2024-02-30", "25/01/2024", ""] {
+            assert_eq!(formatter.format(cell), None, "{}", cell);
+            assert_eq!(formatter.format_cell(cell), cell);
+        }
+    }
+
+    #[test]
+    fn test_options_choose_output_format() {
+        let options = |input: Option<&str>, output: Option<&str>, locale: Option<&str>| {
+            ExportOptions {
+                date_input_format: input.map(str::to_string),
+                date_output_format: output.map(str::to_string),
+                locale: locale.map(str::to_string),
+                ..Default::default()
+            }
+        };
+
+        assert_eq!(DateFormatter::from_options(None), None);
+        assert_eq!(DateFormatter::from_options(Some(&options(None, None, None))), None);
+        assert_eq!(
+            DateFormatter::from_options(Some(&options(None, None, Some("de-DE")))),
+            Some(DateFormatter::new(ISO_DATE, "%d.%m.%Y"))
+        );
+        assert_eq!(
+            DateFormatter::from_options(Some(&options(None, Some("%Y/%m/%d"), Some("de-DE")))),
+            Some(DateFormatter::new(ISO_DATE, "%Y/%m/%d"))
+        );
+        assert_eq!(
+            DateFormatter::from_options(Some(&options(Some("%d/%m/%Y"), None, None))),
+            Some(DateFormatter::new("%d/%m/%Y", ISO_DATE))
+        );
+    }
+
+    #[test]
+    fn test_format_strings_are_checked() {
+        assert!(is_valid_format("%d/%m/%Y"));
+        assert!(!is_valid_format("%Q"));
+        assert!(!is_valid_format(" "));
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use super::models::{ExportData, Sheet};
+
+/// Hash of the cells a sheet shows: headers, rows and summary row, but not its title
+fn content_hash(sheet: &Sheet) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    sheet.headers.hash(&mut hasher);
+    sheet.rows.hash(&mut hasher);
+    sheet.summary_row.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn same_content(a: &Sheet, b: &Sheet) -> bool {
+    a.headers == b.headers && a.rows == b.rows && a.summary_row == b.summary_row
+}
+
+/// Indices of the sheets whose content repeats an earlier sheet, when `dedupe_sheets` is set
+///
+/// Sheets are compared by content hash, confirmed cell by cell; titles and column
+/// metadata are ignored.
+fn duplicate_indices(data: &ExportData) -> Vec<usize> {
+    let enabled = data
+        .options
+        .as_ref()
+        .and_then(|o| o.dedupe_sheets)
+        .unwrap_or(false);
+    let Some(sheets) = data.sheets.as_ref().filter(|_| enabled) else {
+        return Vec::new();
+    };
+
+    let mut seen: HashMap<u64, Vec<&Sheet>> = HashMap::new();
+    let mut duplicates = Vec::new();
+    for (idx, sheet) in sheets.iter().enumerate() {
+        let earlier = seen.entry(content_hash(sheet)).or_default();
+        if earlier.iter().any(|kept| same_content(kept, sheet)) {
+            duplicates.push(idx);
+        } else {
+            earlier.push(sheet);
+        }
+    }
+    duplicates
+}
+
+/// Drop every sheet that repeats an earlier one, keeping the first of each content
+///
+/// Returns the titles of the dropped sheets, in request order.
+pub fn dedupe_sheets(data: &mut ExportData) -> Vec<String> {
+    let duplicates = duplicate_indices(data);
+    let Some(sheets) = data.sheets.take() else {
+        return Vec::new();
+    };
+    let mut omitted = Vec::with_capacity(duplicates.len());
+    let kept = sheets
+        .into_iter()
+        .enumerate()
+        .filter_map(|(idx, sheet)| {
+            if duplicates.contains(&idx) {
+                omitted.push(sheet.title);
+                None
+            } else {
+                Some(sheet)
+            }
+        })
+        .collect();
+    data.sheets = Some(kept);
+    omitted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{ExportFormat, ExportOptions};
+
+    fn sheet(title: &str, value: &str) -> Sheet {
+        Sheet {
+            title: title.to_string(),
+            headers: vec!["Item".to_string()],
+            rows: vec![vec![value.to_string()]],
+            column_metadata: None,
+            summary_row: None,
+        }
+    }
+
+    fn workbook(dedupe: Option<bool>) -> ExportData {
+        ExportData {
+            title: "Workbook".to_string(),
+            format: ExportFormat::Excel,
+            headers: Vec::new(),
+            rows: Vec::new(),
+            options: Some(ExportOptions {
+                dedupe_sheets: dedupe,
+                ..Default::default()
+            }),
+            column_metadata: None,
+            summary_row: None,
+            sheets: Some(vec![
+                sheet("Q1", "Widget"),
+                sheet("Q1 (copy)", "Widget"),
+                sheet("Q2", "Gadget"),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_identical_sheets_collapse_to_one() {
+        let mut data = workbook(Some(true));
+
+        assert_eq!(dedupe_sheets(&mut data), vec!["Q1 (copy)"]);
+        let titles: Vec<&str> = data.sheets.iter().flatten().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["Q1", "Q2"]);
+    }
+
+    #[test]
+    fn test_sheets_are_kept_unless_dedupe_is_enabled() {
+        let mut data = workbook(None);
+
+        assert!(dedupe_sheets(&mut data).is_empty());
+        assert_eq!(data.sheets.map(|sheets| sheets.len()), Some(3));
+    }
+}
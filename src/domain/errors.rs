@@ -4,22 +4,45 @@ use std::fmt;
 #[derive(Debug)]
 pub enum DomainError {
     InvalidFormat(String),
+    InvalidOption(String),
     EmptyData(String),
     ColumnCountMismatch {
         row: usize,
         expected: usize,
         actual: usize,
     },
+    SummaryColumnCountMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    MetadataColumnCountMismatch {
+        expected: usize,
+        actual: usize,
+    },
     CellTooLong(usize),
-    TooManyRows(usize),
+    TooManyRows {
+        count: usize,
+        max: usize,
+    },
+    TooManyColumns {
+        count: usize,
+        max: usize,
+    },
+    OutputTooLarge {
+        estimated: usize,
+        max: usize,
+    },
+    MissingCredentials,
     InvalidToken,
     TokenExpired,
+    TokenGeneration(String),
 }
 
 impl fmt::Display for DomainError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             DomainError::InvalidFormat(msg) => write!(f, "Invalid format: {}", msg),
+            DomainError::InvalidOption(msg) => write!(f, "Invalid option: {}", msg),
             DomainError::EmptyData(msg) => write!(f, "Empty data: {}", msg),
             DomainError::ColumnCountMismatch {
                 row,
@@ -30,10 +53,32 @@ impl fmt::Display for DomainError {
                 "Row {}: column count mismatch (expected {}, got {})",
                 row, expected, actual
             ),
+            DomainError::SummaryColumnCountMismatch { expected, actual } => write!(
+                f,
+                "Summary row: column count mismatch (expected {}, got {})",
+                expected, actual
+            ),
+            DomainError::MetadataColumnCountMismatch { expected, actual } => write!(
+                f,
+                "Column metadata: column count mismatch (expected {}, got {})",
+                expected, actual
+            ),
             DomainError::CellTooLong(len) => write!(f, "Cell content too long: {} chars", len),
-            DomainError::TooManyRows(count) => write!(f, "Too many rows: {} (max 10000)", count),
+            DomainError::TooManyRows { count, max } => {
+                write!(f, "Too many rows: {} (max {})", count, max)
+            }
+            DomainError::TooManyColumns { count, max } => {
+                write!(f, "Too many columns: {} (max {})", count, max)
+            }
+            DomainError::OutputTooLarge { estimated, max } => write!(
+                f,
+                "Export too large: estimated {} bytes (max {})",
+                estimated, max
+            ),
+            DomainError::MissingCredentials => write!(f, "Missing credentials"),
             DomainError::InvalidToken => write!(f, "Invalid token"),
             DomainError::TokenExpired => write!(f, "Token expired"),
+            DomainError::TokenGeneration(msg) => write!(f, "Token generation failed: {}", msg),
         }
     }
 }
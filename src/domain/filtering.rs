@@ -0,0 +1,107 @@
+use super::models::{ExportData, FilterOp, FilterOption};
+use std::cmp::Ordering;
+
+/// Drop the rows that do not match the `filter` option when it is set
+///
+/// Number, currency and percentage columns compare numerically for `eq`, `ne`, `gt` and
+/// `lt`; a cell or value that does not parse as a number never matches `gt`/`lt` and is
+/// compared as text for `eq`/`ne`. Other columns compare as strings. `contains` is always
+/// a substring match.
+pub fn apply_filter(data: &mut ExportData) {
+    let Some(filter) = data.options.as_ref().and_then(|o| o.filter.clone()) else {
+        return;
+    };
+    let Some(col_idx) = filter.column.resolve(&data.headers) else {
+        return;
+    };
+    let numeric = data.column_type(col_idx).is_right_aligned();
+
+    data.rows.retain(|row| {
+        let cell = row.get(col_idx).map(String::as_str).unwrap_or("");
+        matches(cell, &filter, numeric)
+    });
+}
+
+fn matches(cell: &str, filter: &FilterOption, numeric: bool) -> bool {
+    let ordering = if numeric {
+        let number = |s: &str| s.trim().parse::<f64>().ok();
+        match (number(cell), number(&filter.value)) {
+            (Some(cell), Some(value)) => Some(cell.total_cmp(&value)),
+            _ => None,
+        }
+    } else {
+        Some(cell.cmp(filter.value.as_str()))
+    };
+
+    match filter.op {
+        FilterOp::Eq => ordering.map_or(cell == filter.value, Ordering::is_eq),
+        FilterOp::Ne => ordering.map_or(cell != filter.value, Ordering::is_ne),
+        FilterOp::Gt => ordering == Some(Ordering::Greater),
+        FilterOp::Lt => ordering == Some(Ordering::Less),
+        FilterOp::Contains => cell.contains(filter.value.as_str()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{ColumnMetadata, ColumnRef, ExportFormat, ExportOptions};
+
+    fn filtered(column: &str, op: FilterOp, value: &str) -> Vec<String> {
+        let mut data = ExportData {
+            title: "Accounts".to_string(),
+            format: ExportFormat::Csv,
+            headers: vec![
+                "Name".to_string(),
+                "Status".to_string(),
+                "Balance".to_string(),
+            ],
+            rows: [
+                ("Ann", "active", "9"),
+                ("Bob", "inactive", "120"),
+                ("Cy", "active", "15.5"),
+                ("Di", "active", "n/a"),
+            ]
+            .iter()
+            .map(|(name, status, balance)| {
+                vec![name.to_string(), status.to_string(), balance.to_string()]
+            })
+            .collect(),
+            options: Some(ExportOptions {
+                filter: Some(FilterOption {
+                    column: ColumnRef::Name(column.to_string()),
+                    op,
+                    value: value.to_string(),
+                }),
+                ..Default::default()
+            }),
+            column_metadata: Some(vec![
+                ColumnMetadata::text(),
+                ColumnMetadata::text(),
+                ColumnMetadata::number(),
+            ]),
+            summary_row: None,
+            sheets: None,
+        };
+        apply_filter(&mut data);
+        data.rows.into_iter().map(|row| row[0].clone()).collect()
+    }
+
+    #[test]
+    fn test_eq_keeps_only_matching_rows() {
+        assert_eq!(
+            filtered("Status", FilterOp::Eq, "active"),
+            vec!["Ann", "Cy", "Di"]
+        );
+        assert_eq!(filtered("Status", FilterOp::Ne, "active"), vec!["Bob"]);
+        assert_eq!(filtered("Status", FilterOp::Contains, "in"), vec!["Bob"]);
+    }
+
+    #[test]
+    fn test_numeric_columns_compare_as_numbers() {
+        // As text "9" > "15.5" and "120" < "15.5"; numerically it is the other way round
+        assert_eq!(filtered("Balance", FilterOp::Gt, "10"), vec!["Bob", "Cy"]);
+        assert_eq!(filtered("Balance", FilterOp::Lt, "15.5"), vec!["Ann"]);
+        assert_eq!(filtered("Balance", FilterOp::Eq, "15.50"), vec!["Cy"]);
+    }
+}
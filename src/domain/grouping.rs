@@ -0,0 +1,88 @@
+use super::models::{ExportData, TableView};
+
+/// A row in export order once group headers have been inserted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GroupedRow<'a> {
+    /// Start of a group, carrying the grouping column's value
+    GroupHeader(&'a str),
+    Data(&'a [String]),
+}
+
+/// The data rows with a group header before each run of equal `group_by` values
+///
+/// Groups are consecutive runs, so rows should already be ordered by the grouping
+/// column (e.g. with `sort_by`). Without `group_by` only data rows are returned.
+pub fn grouped_rows(data: &ExportData) -> Vec<GroupedRow<'_>> {
+    grouped_table_rows(data.table())
+}
+
+/// `grouped_rows` for a borrowed table
+pub fn grouped_table_rows(table: TableView<'_>) -> Vec<GroupedRow<'_>> {
+    let group_col = table
+        .options
+        .and_then(|o| o.group_by.as_ref())
+        .and_then(|column| column.resolve(table.headers));
+    let Some(group_col) = group_col else {
+        return table.rows.iter().map(|row| GroupedRow::Data(row)).collect();
+    };
+
+    let mut rows = Vec::with_capacity(table.rows.len() + 1);
+    let mut current: Option<&str> = None;
+    for row in table.rows {
+        let key = row.get(group_col).map(String::as_str).unwrap_or("");
+        if current != Some(key) {
+            rows.push(GroupedRow::GroupHeader(key));
+            current = Some(key);
+        }
+        rows.push(GroupedRow::Data(row));
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{ColumnRef, ExportFormat, ExportOptions};
+
+    fn regional_data(group_by: Option<ColumnRef>) -> ExportData {
+        ExportData {
+            title: "Sales".to_string(),
+            format: ExportFormat::Csv,
+            headers: vec!["Region".to_string(), "Amount".to_string()],
+            rows: vec![
+                vec!["North".to_string(), "10".to_string()],
+                vec!["North".to_string(), "20".to_string()],
+                vec!["South".to_string(), "5".to_string()],
+            ],
+            options: Some(ExportOptions {
+                group_by,
+                ..Default::default()
+            }),
+            column_metadata: None,
+            summary_row: None,
+            sheets: None,
+        }
+    }
+
+    #[test]
+    fn test_header_inserted_when_group_changes() {
+        let data = regional_data(Some(ColumnRef::Name("Region".to_string())));
+        let rows = grouped_rows(&data);
+
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[0], GroupedRow::GroupHeader("North"));
+        assert_eq!(rows[1], GroupedRow::Data(&data.rows[0]));
+        assert_eq!(rows[2], GroupedRow::Data(&data.rows[1]));
+        assert_eq!(rows[3], GroupedRow::GroupHeader("South"));
+        assert_eq!(rows[4], GroupedRow::Data(&data.rows[2]));
+    }
+
+    #[test]
+    fn test_no_grouping_returns_data_rows() {
+        let data = regional_data(None);
+        let rows = grouped_rows(&data);
+
+        assert_eq!(rows.len(), 3);
+        assert!(rows.iter().all(|row| matches!(row, GroupedRow::Data(_))));
+    }
+}
@@ -0,0 +1,118 @@
+use super::models::{ColumnMetadata, ColumnType, ExportData};
+use chrono::NaiveDate;
+
+/// Assign a column type to every column without metadata when `infer_types` is set
+///
+/// A column is `Number` when every non-empty cell is numeric, `Date` when every
+/// non-empty cell is an ISO date (`YYYY-MM-DD`), and `Text` otherwise. Explicit
+/// metadata is never overridden.
+pub fn infer_column_types(data: &mut ExportData) {
+    let enabled = data
+        .options
+        .as_ref()
+        .and_then(|o| o.infer_types)
+        .unwrap_or(false);
+    if !enabled {
+        return;
+    }
+
+    let mut metadata = data.column_metadata.take().unwrap_or_default();
+    for col_idx in metadata.len()..data.headers.len() {
+        let cells = data.rows.iter().filter_map(|row| row.get(col_idx));
+        metadata.push(ColumnMetadata {
+            column_type: infer_type(cells.map(String::as_str)),
+            ..Default::default()
+        });
+    }
+    data.column_metadata = Some(metadata);
+}
+
+fn infer_type<'a>(cells: impl Iterator<Item = &'a str>) -> ColumnType {
+    let mut values = cells
+        .map(str::trim)
+        .filter(|cell| !cell.is_empty())
+        .peekable();
+    let Some(first) = values.peek().copied() else {
+        return ColumnType::Text;
+    };
+
+    let candidate = if is_number(first) {
+        ColumnType::Number
+    } else if is_date(first) {
+        ColumnType::Date
+    } else {
+        return ColumnType::Text;
+    };
+
+    let matches = |cell: &str| match candidate {
+        ColumnType::Number => is_number(cell),
+        _ => is_date(cell),
+    };
+    if values.all(matches) {
+        candidate
+    } else {
+        ColumnType::Text
+    }
+}
+
+/// Numeric value; codes with leading zeros (e.g. `00123`) are not numbers
+fn is_number(cell: &str) -> bool {
+    let digits = cell.trim_start_matches(['-', '+']);
+    let has_leading_zero =
+        digits.len() > 1 && digits.starts_with('0') && digits.as_bytes()[1].is_ascii_digit();
+    !has_leading_zero && cell.parse::<f64>().is_ok_and(f64::is_finite)
+}
+
+fn is_date(cell: &str) -> bool {
+    NaiveDate::parse_from_str(cell, "%Y-%m-%d").is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{ExportFormat, ExportOptions};
+
+    fn inferred(rows: Vec<Vec<&str>>, metadata: Option<Vec<ColumnMetadata>>) -> Vec<ColumnType> {
+        let mut data = ExportData {
+            title: "Inference".to_string(),
+            format: ExportFormat::Csv,
+            headers: (0..rows[0].len()).map(|i| format!("Col {}", i)).collect(),
+            rows: rows
+                .into_iter()
+                .map(|row| row.into_iter().map(String::from).collect())
+                .collect(),
+            options: Some(ExportOptions {
+                infer_types: Some(true),
+                ..Default::default()
+            }),
+            column_metadata: metadata,
+            summary_row: None,
+            sheets: None,
+        };
+        infer_column_types(&mut data);
+        (0..data.headers.len())
+            .map(|i| data.column_type(i))
+            .collect()
+    }
+
+    #[test]
+    fn test_integer_column_is_number() {
+        let types = inferred(vec![vec!["1"], vec!["250"], vec![""], vec!["-3"]], None);
+        assert_eq!(types, vec![ColumnType::Number]);
+    }
+
+    #[test]
+    fn test_mixed_column_stays_text() {
+        let types = inferred(vec![vec!["1", "00123"], vec!["n/a", "00456"]], None);
+        assert_eq!(types, vec![ColumnType::Text, ColumnType::Text]);
+    }
+
+    #[test]
+    fn test_date_column_and_explicit_metadata() {
+        let types = inferred(
+            vec![vec!["42", "2024-01-15"], vec!["7", "2024-02-01"]],
+            Some(vec![ColumnMetadata::currency()]),
+        );
+        assert_eq!(types, vec![ColumnType::Currency, ColumnType::Date]);
+    }
+}
@@ -0,0 +1,169 @@
+use chrono::NaiveDate;
+use super::models::ColumnType;
+
+/// Locale-specific conventions for rendering numbers, currencies and dates
+#[derive(Debug, Clone, PartialEq)]
+pub struct Locale {
+    pub decimal_separator: char,
+    pub thousands_separator: char,
+    /// chrono format string used for `Date` columns
+    pub date_format: &'static str,
+    pub currency_symbol: &'static str,
+    /// Whether the currency symbol goes before the amount (`$1.00`) or after it (`1,00 €`)
+    pub currency_prefix: bool,
+}
+
+impl Locale {
+    pub fn en_us() -> Self {
+        Self {
+            decimal_separator: '.',
+            thousands_separator: ',',
+            date_format: "%m/%d/%Y",
+            currency_symbol: "$",
+            currency_prefix: true,
+        }
+    }
+
+    pub fn en_gb() -> Self {
+        Self {
+            decimal_separator: '.',
+            thousands_separator: ',',
+            date_format: "%d/%m/%Y",
+            currency_symbol: "£",
+            currency_prefix: true,
+        }
+    }
+
+    pub fn de_de() -> Self {
+        Self {
+            decimal_separator: ',',
+            thousands_separator: '.',
+            date_format: "%d.%m.%Y",
+            currency_symbol: "€",
+            currency_prefix: false,
+        }
+    }
+
+    pub fn th_th() -> Self {
+        Self {
+            decimal_separator: '.',
+            thousands_separator: ',',
+            date_format: "%d/%m/%Y",
+            currency_symbol: "฿",
+            currency_prefix: true,
+        }
+    }
+
+    /// Look up a locale by its language tag (e.g. `th-TH`, `en_us`)
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag.replace('_', "-").to_lowercase().as_str() {
+            "en-us" => Some(Self::en_us()),
+            "en-gb" => Some(Self::en_gb()),
+            "de-de" => Some(Self::de_de()),
+            "th-th" => Some(Self::th_th()),
+            _ => None,
+        }
+    }
+
+    /// Format a plain decimal string (e.g. `-1234.50`) with this locale's separators.
+    /// Returns None when the value is not a plain decimal number.
+    pub fn format_number(&self, raw: &str) -> Option<String> {
+        let trimmed = raw.trim();
+        let (sign, unsigned) = match trimmed.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => ("", trimmed),
+        };
+        let (int_part, frac_part) = match unsigned.split_once('.') {
+            Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+            None => (unsigned, None),
+        };
+
+        let is_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+        if !is_digits(int_part) || frac_part.is_some_and(|frac| !is_digits(frac)) {
+            return None;
+        }
+
+        let mut grouped = String::with_capacity(int_part.len() + int_part.len() / 3);
+        for (i, digit) in int_part.chars().enumerate() {
+            if i > 0 && (int_part.len() - i) % 3 == 0 {
+                grouped.push(self.thousands_separator);
+            }
+            grouped.push(digit);
+        }
+
+        Some(match frac_part {
+            Some(frac) => format!("{}{}{}{}", sign, grouped, self.decimal_separator, frac),
+            None => format!("{}{}", sign, grouped),
+        })
+    }
+
+    /// Format an ISO-8601 date (`YYYY-MM-DD`) with this locale's date format
+    pub fn format_date(&self, raw: &str) -> Option<String> {
+        NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d")
+            .ok()
+            .map(|date| date.format(self.date_format).to_string())
+    }
+
+    /// Format a cell according to its column type, passing unparseable values through unchanged
+    pub fn format_cell(&self, raw: &str, column_type: ColumnType) -> String {
+        let formatted = match column_type {
+            ColumnType::Text | ColumnType::QrCode => None,
+            ColumnType::Number => self.format_number(raw),
+            ColumnType::Currency => self.format_number(raw).map(|amount| {
+                if self.currency_prefix {
+                    format!("{}{}", self.currency_symbol, amount)
+                } else {
+                    format!("{} {}", amount, self.currency_symbol)
+                }
+            }),
+            ColumnType::Percentage => {
+                let value = raw.trim();
+                self.format_number(value.strip_suffix('%').unwrap_or(value))
+                    .map(|number| format!("{}%", number))
+            }
+            ColumnType::Date => self.format_date(raw),
+        };
+        formatted.unwrap_or_else(|| raw.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_tag() {
+        assert_eq!(Locale::from_tag("de-DE"), Some(Locale::de_de()));
+        assert_eq!(Locale::from_tag("en_us"), Some(Locale::en_us()));
+        assert_eq!(Locale::from_tag("xx-XX"), None);
+    }
+
+    #[test]
+    fn test_format_number_grouping() {
+        let en = Locale::en_us();
+        assert_eq!(en.format_number("1234567.89").as_deref(), Some("1,234,567.89"));
+        assert_eq!(en.format_number("-1000").as_deref(), Some("-1,000"));
+        assert_eq!(en.format_number("999").as_deref(), Some("999"));
+
+        let de = Locale::de_de();
+        assert_eq!(de.format_number("1234567.89").as_deref(), Some("1.234.567,89"));
+
+        assert_eq!(en.format_number("12abc"), None);
+        assert_eq!(en.format_number("1e5"), None);
+        assert_eq!(en.format_number(""), None);
+    }
+
+    #[test]
+    fn test_format_cell_by_type() {
+        let de = Locale::de_de();
+        assert_eq!(de.format_cell("1234.5", ColumnType::Currency), "1.234,5 €");
+        assert_eq!(de.format_cell("12.5%", ColumnType::Percentage), "12,5%");
+        assert_eq!(de.format_cell("2024-03-01", ColumnType::Date), "01.03.2024");
+        assert_eq!(de.format_cell("1234.5", ColumnType::Text), "1234.5");
+        assert_eq!(de.format_cell("n/a", ColumnType::Number), "n/a");
+
+        let us = Locale::en_us();
+        assert_eq!(us.format_cell("1234.5", ColumnType::Currency), "$1,234.5");
+        assert_eq!(us.format_cell("2024-03-01", ColumnType::Date), "03/01/2024");
+    }
+}
@@ -1,3 +1,15 @@
 pub mod models;
 pub mod errors;
 pub mod validators;
+pub mod locale;
+pub mod dates;
+pub mod filtering;
+pub mod sampling;
+pub mod inference;
+pub mod sorting;
+pub mod grouping;
+pub mod trimming;
+pub mod summary;
+pub mod dedupe;
+pub mod sizing;
+pub mod builder;
@@ -1,7 +1,8 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Column data type for proper formatting and alignment
-#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ColumnType {
     #[default]
@@ -10,50 +11,278 @@ pub enum ColumnType {
     Currency,
     Percentage,
     Date,
+    // Drawn as a QR code of the cell value in PDF; other formats keep the raw value
+    QrCode,
 }
 
 impl ColumnType {
+    /// Parse a type name as used in requests (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "text" => Some(Self::Text),
+            "number" => Some(Self::Number),
+            "currency" => Some(Self::Currency),
+            "percentage" => Some(Self::Percentage),
+            "date" => Some(Self::Date),
+            "qrcode" => Some(Self::QrCode),
+            _ => None,
+        }
+    }
+
     /// Returns true if this column type should be right-aligned
     pub fn is_right_aligned(&self) -> bool {
         matches!(self, Self::Number | Self::Currency | Self::Percentage)
     }
 }
 
+/// How a cell longer than its column is shown (PDF only)
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CellOverflow {
+    /// Cut to a single line with an ellipsis
+    #[default]
+    Truncate,
+    /// Wrap onto as many lines as needed; the row grows to fit
+    Wrap,
+}
+
+/// Horizontal alignment of a column's cells
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Alignment {
+    Left,
+    Center,
+    Right,
+}
+
 /// Metadata for a single column
-#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
 pub struct ColumnMetadata {
     /// Column data type (affects alignment and formatting)
     #[serde(default)]
     pub column_type: ColumnType,
     /// Optional custom width hint (percentage or fixed)
     pub width_hint: Option<f32>,
+    /// Always treat values as text (e.g. codes like `00123`), never as numbers
+    #[serde(default)]
+    pub force_text: bool,
+    /// Truncate (default) or wrap cells that do not fit the column
+    #[serde(default)]
+    pub overflow: CellOverflow,
+    /// Keep leading/trailing whitespace even when `trim_cells` is set
+    #[serde(default)]
+    pub preserve_whitespace: bool,
+    /// Line up decimal points of right-aligned values instead of their last digits (PDF only)
+    #[serde(default)]
+    pub decimal_align: bool,
+    /// Align cells this way instead of by `column_type` (PDF and Excel)
+    pub align: Option<Alignment>,
 }
 
 impl ColumnMetadata {
+    fn of_type(column_type: ColumnType) -> Self {
+        Self { column_type, ..Default::default() }
+    }
+
     pub fn text() -> Self {
-        Self { column_type: ColumnType::Text, width_hint: None }
+        Self::of_type(ColumnType::Text)
     }
 
     pub fn number() -> Self {
-        Self { column_type: ColumnType::Number, width_hint: None }
+        Self::of_type(ColumnType::Number)
     }
 
     pub fn currency() -> Self {
-        Self { column_type: ColumnType::Currency, width_hint: None }
+        Self::of_type(ColumnType::Currency)
     }
 
     pub fn percentage() -> Self {
-        Self { column_type: ColumnType::Percentage, width_hint: None }
+        Self::of_type(ColumnType::Percentage)
     }
 
     pub fn date() -> Self {
-        Self { column_type: ColumnType::Date, width_hint: None }
+        Self::of_type(ColumnType::Date)
+    }
+
+    pub fn qr_code() -> Self {
+        Self::of_type(ColumnType::QrCode)
     }
 
     pub fn with_width(mut self, width: f32) -> Self {
         self.width_hint = Some(width);
         self
     }
+
+    pub fn with_force_text(mut self) -> Self {
+        self.force_text = true;
+        self
+    }
+
+    pub fn with_overflow(mut self, overflow: CellOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    pub fn with_preserve_whitespace(mut self) -> Self {
+        self.preserve_whitespace = true;
+        self
+    }
+
+    pub fn with_decimal_align(mut self) -> Self {
+        self.decimal_align = true;
+        self
+    }
+
+    pub fn with_align(mut self, align: Alignment) -> Self {
+        self.align = Some(align);
+        self
+    }
+}
+
+/// Reference to a column by zero-based index or header name
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ColumnRef {
+    Index(usize),
+    Name(String),
+}
+
+impl ColumnRef {
+    /// Resolve to a column index, or None if the column does not exist
+    pub fn resolve(&self, headers: &[String]) -> Option<usize> {
+        match self {
+            ColumnRef::Index(idx) => (*idx < headers.len()).then_some(*idx),
+            ColumnRef::Name(name) => headers.iter().position(|h| h == name),
+        }
+    }
+}
+
+/// How over-long PDF cells are shortened
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TruncationMode {
+    /// Simple character-based truncation (fast but may break words)
+    Simple,
+    /// Smart word-boundary aware truncation using textwrap
+    #[default]
+    WordBoundary,
+}
+
+/// Render a delimited cell value (e.g. `a|b|c`) as a bulleted list (PDF only)
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct BulletListOption {
+    pub column: ColumnRef,
+    pub delimiter: String,
+}
+
+/// Export a reproducible random sample of the rows instead of all of them
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct SampleOption {
+    /// Number of rows to keep
+    pub size: usize,
+    /// Seed for the random generator; the same seed and size give the same rows
+    pub seed: u64,
+}
+
+/// Character encoding of CSV output
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CsvEncoding {
+    #[default]
+    Utf8,
+    /// UTF-16 little endian with a leading `FF FE` byte order mark
+    Utf16le,
+}
+
+/// Shape of JSON output
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonLayout {
+    /// One object per row, keyed by header
+    #[default]
+    Objects,
+    /// One array per row, preceded by an array of the headers
+    Arrays,
+}
+
+/// How the values of percentage columns are expressed
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PercentScale {
+    /// Whole percents, e.g. `12.5` for 12.5%
+    #[default]
+    Whole,
+    /// Fractions of one, e.g. `0.125` for 12.5%
+    Fraction,
+}
+
+/// Page orientation of PDF output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Orientation {
+    #[default]
+    Portrait,
+    /// Width and height swapped, for tables with many columns
+    Landscape,
+}
+
+/// Direction for `sort_by`
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Statistic computed for numeric columns by `summary_stats`
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SummaryStat {
+    #[default]
+    Sum,
+    Mean,
+}
+
+/// Sort rows by one column before exporting
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct SortOption {
+    pub column: ColumnRef,
+    #[serde(default)]
+    pub order: SortOrder,
+}
+
+/// Comparison used by a row filter
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Lt,
+    /// Cell contains the value as a substring
+    Contains,
+}
+
+/// Keep only rows whose cell in `column` satisfies `op` against `value`
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct FilterOption {
+    pub column: ColumnRef,
+    pub op: FilterOp,
+    pub value: String,
+}
+
+/// One worksheet of a multi-sheet Excel export
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct Sheet {
+    /// Worksheet name (at most 31 characters, unique within the workbook)
+    pub title: String,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    #[serde(default)]
+    pub column_metadata: Option<Vec<ColumnMetadata>>,
+    #[serde(default)]
+    pub summary_row: Option<Vec<String>>,
 }
 
 /// Main export data structure
@@ -67,6 +296,111 @@ pub struct ExportData {
     /// Optional column metadata for proper formatting
     /// If None or shorter than headers, defaults are used
     pub column_metadata: Option<Vec<ColumnMetadata>>,
+    /// Optional totals row rendered after all data rows; must match the header count
+    pub summary_row: Option<Vec<String>>,
+    /// Worksheets exported instead of the top-level table (Excel only)
+    pub sheets: Option<Vec<Sheet>>,
+}
+
+impl ExportData {
+    /// A single-table export of `sheet`, sharing this export's format and options
+    pub fn for_sheet(&self, sheet: Sheet) -> ExportData {
+        ExportData {
+            title: sheet.title,
+            format: self.format,
+            headers: sheet.headers,
+            rows: sheet.rows,
+            options: self.options.clone(),
+            column_metadata: sheet.column_metadata,
+            summary_row: sheet.summary_row,
+            sheets: None,
+        }
+    }
+
+    /// The top-level table, borrowed
+    pub fn table(&self) -> TableView<'_> {
+        TableView {
+            headers: &self.headers,
+            rows: &self.rows,
+            options: self.options.as_ref(),
+            column_metadata: self.column_metadata.as_deref(),
+            summary_row: self.summary_row.as_deref(),
+        }
+    }
+
+    /// `sheet` as a table with this export's options, borrowed rather than copied
+    pub fn sheet_table<'a>(&'a self, sheet: &'a Sheet) -> TableView<'a> {
+        TableView {
+            headers: &sheet.headers,
+            rows: &sheet.rows,
+            options: self.options.as_ref(),
+            column_metadata: sheet.column_metadata.as_deref(),
+            summary_row: sheet.summary_row.as_deref(),
+        }
+    }
+
+    /// Effective column type for the given column; `Text` when no metadata is present
+    /// or the column is forced to text
+    pub fn column_type(&self, col_idx: usize) -> ColumnType {
+        self.table().column_type(col_idx)
+    }
+
+    /// Explicit alignment of the given column, if its metadata sets one
+    pub fn column_alignment(&self, col_idx: usize) -> Option<Alignment> {
+        self.table().column_alignment(col_idx)
+    }
+
+    /// Whether the given column is marked to always be written as text
+    pub fn is_forced_text(&self, col_idx: usize) -> bool {
+        self.table().is_forced_text(col_idx)
+    }
+}
+
+/// A borrowed table: the top-level one or a sheet, with the export's options
+#[derive(Debug, Clone, Copy)]
+pub struct TableView<'a> {
+    pub headers: &'a [String],
+    pub rows: &'a [Vec<String>],
+    pub options: Option<&'a ExportOptions>,
+    pub column_metadata: Option<&'a [ColumnMetadata]>,
+    pub summary_row: Option<&'a [String]>,
+}
+
+impl TableView<'_> {
+    fn metadata(&self, col_idx: usize) -> Option<&ColumnMetadata> {
+        self.column_metadata.and_then(|metadata| metadata.get(col_idx))
+    }
+
+    /// Effective column type for the given column; `Text` when no metadata is present
+    /// or the column is forced to text
+    pub fn column_type(&self, col_idx: usize) -> ColumnType {
+        self.metadata(col_idx)
+            .filter(|meta| !meta.force_text)
+            .map(|meta| meta.column_type)
+            .unwrap_or_default()
+    }
+
+    /// Explicit alignment of the given column, if its metadata sets one
+    pub fn column_alignment(&self, col_idx: usize) -> Option<Alignment> {
+        self.metadata(col_idx).and_then(|meta| meta.align)
+    }
+
+    /// Whether the given column is marked to always be written as text
+    pub fn is_forced_text(&self, col_idx: usize) -> bool {
+        self.metadata(col_idx).is_some_and(|meta| meta.force_text)
+    }
+}
+
+impl From<ExportData> for Sheet {
+    fn from(data: ExportData) -> Self {
+        Sheet {
+            title: data.title,
+            headers: data.headers,
+            rows: data.rows,
+            column_metadata: data.column_metadata,
+            summary_row: data.summary_row,
+        }
+    }
 }
 
 /// Export format types
@@ -75,37 +409,133 @@ pub enum ExportFormat {
     Excel,
     Csv,
     Pdf,
+    Json,
+    Html,
+    /// Tab-separated values, written by the CSV exporter with a tab delimiter
+    Tsv,
 }
 
 impl ExportFormat {
+    /// All supported formats
+    pub const ALL: [ExportFormat; 6] = [
+        ExportFormat::Excel,
+        ExportFormat::Csv,
+        ExportFormat::Pdf,
+        ExportFormat::Json,
+        ExportFormat::Html,
+        ExportFormat::Tsv,
+    ];
+
+    /// Parse a format name as sent by clients (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Get the format name used in requests
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportFormat::Excel => "excel",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Pdf => "pdf",
+            ExportFormat::Json => "json",
+            ExportFormat::Html => "html",
+            ExportFormat::Tsv => "tsv",
+        }
+    }
+
     /// Get file extension
-    pub fn extension(&self) -> &str {
+    pub fn extension(&self) -> &'static str {
         match self {
             ExportFormat::Excel => "xlsx",
             ExportFormat::Csv => "csv",
             ExportFormat::Pdf => "pdf",
+            ExportFormat::Json => "json",
+            ExportFormat::Html => "html",
+            ExportFormat::Tsv => "tsv",
         }
     }
 
     /// Get MIME type
-    pub fn mime_type(&self) -> &str {
+    pub fn mime_type(&self) -> &'static str {
         match self {
             ExportFormat::Excel => {
                 "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
             }
             ExportFormat::Csv => "text/csv",
             ExportFormat::Pdf => "application/pdf",
+            ExportFormat::Json => "application/json",
+            ExportFormat::Html => "text/html; charset=utf-8",
+            ExportFormat::Tsv => "text/tab-separated-values",
         }
     }
 }
 
 /// Export options for formatting
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
 pub struct ExportOptions {
     pub freeze_headers: Option<bool>,
     pub auto_fit_columns: Option<bool>,
+    /// Add Excel filter dropdowns to the header row, covering every data row
+    pub autofilter: Option<bool>,
     pub header_bold: Option<bool>,
     pub header_background: Option<String>,
     pub include_header_row: Option<bool>,
     pub delimiter: Option<String>,
+    /// Replace embedded newlines in CSV cells with this string (default: keep them quoted)
+    pub newline_replacement: Option<String>,
+    /// Language tag (e.g. `th-TH`, `en-US`, `de-DE`) for number, currency and date formatting
+    pub locale: Option<String>,
+    /// chrono format `date` cells are sent in (default `%Y-%m-%d`)
+    pub date_input_format: Option<String>,
+    /// chrono format `date` cells are rendered in by CSV and PDF (default: the `locale`'s
+    /// date format, else ISO)
+    pub date_output_format: Option<String>,
+    /// Render one column's delimited values as bullet lines (PDF only)
+    pub bullet_list: Option<BulletListOption>,
+    /// Prefix for the first field of the CSV summary record (e.g. `TOTAL`, or `TOTAL: Total`)
+    pub summary_marker: Option<String>,
+    /// How over-long PDF cells are truncated (`simple` or `word_boundary`)
+    pub truncation_mode: Option<TruncationMode>,
+    /// Marker appended to truncated PDF cells (default `...`, at most 3 chars)
+    pub ellipsis: Option<String>,
+    /// Deterministically sample rows before exporting
+    pub sample: Option<SampleOption>,
+    /// Infer number/date/text column types for columns without metadata
+    pub infer_types: Option<bool>,
+    /// Skip sheets whose cells repeat an earlier sheet (Excel `sheets` only)
+    pub dedupe_sheets: Option<bool>,
+    /// Write empty cells as `null` in JSON output (tabular formats keep them blank)
+    pub json_null_on_empty: Option<bool>,
+    /// Rows as objects keyed by header (default) or as arrays after a header array (JSON only)
+    pub json_layout: Option<JsonLayout>,
+    /// Sort rows by a column; numeric column types compare as numbers
+    pub sort_by: Option<SortOption>,
+    /// Insert a group header row whenever this column's value changes (CSV, Excel, PDF)
+    pub group_by: Option<ColumnRef>,
+    /// Character encoding of CSV output (default UTF-8)
+    pub csv_encoding: Option<CsvEncoding>,
+    /// Start UTF-8 CSV output with a byte order mark so Excel detects the encoding
+    pub csv_bom: Option<bool>,
+    /// Trim leading/trailing whitespace from data and summary cells
+    pub trim_cells: Option<bool>,
+    /// Render a centered cover page with the logo, title, subtitle and date before the
+    /// table (PDF only)
+    pub cover_page: Option<bool>,
+    /// Line shown under the title on the cover page (PDF only)
+    pub subtitle: Option<String>,
+    /// Start a group on a new page rather than split it, when it fits on one page (PDF only)
+    pub keep_groups_together: Option<bool>,
+    /// Compute `summary_row` from the numeric columns when none is sent
+    pub summary_stats: Option<SummaryStat>,
+    /// Print page numbers (default true); without them the reserved footer space holds rows
+    pub page_numbers: Option<bool>,
+    /// Export only the rows matching this predicate
+    pub filter: Option<FilterOption>,
+    /// Page orientation (`portrait` or `landscape`); overrides the exporter's default (PDF only)
+    pub orientation: Option<Orientation>,
+    /// Whether percentage cells hold whole percents (`12.5`, default) or fractions (`0.125`)
+    /// (Excel only)
+    pub percent_scale: Option<PercentScale>,
 }
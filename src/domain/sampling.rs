@@ -0,0 +1,89 @@
+use super::models::ExportData;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Replace the rows with a reproducible random sample when the `sample` option is set
+///
+/// Sampled rows keep their original order and the title is annotated so readers know
+/// the export is partial. Nothing changes when the sample covers every row.
+pub fn apply_sample(data: &mut ExportData) {
+    let Some(sample) = data.options.as_ref().and_then(|o| o.sample.as_ref()) else {
+        return;
+    };
+    let total = data.rows.len();
+    if sample.size >= total {
+        return;
+    }
+
+    let mut rng = StdRng::seed_from_u64(sample.seed);
+    let mut indices = rand::seq::index::sample(&mut rng, total, sample.size).into_vec();
+    indices.sort_unstable();
+
+    let mut rows = std::mem::take(&mut data.rows);
+    data.rows = indices
+        .into_iter()
+        .map(|idx| std::mem::take(&mut rows[idx]))
+        .collect();
+    data.title = format!(
+        "{} (sample of {} of {} rows, seed {})",
+        data.title, sample.size, total, sample.seed
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{ExportFormat, ExportOptions, SampleOption};
+
+    fn numbered_data(rows: usize, sample: Option<SampleOption>) -> ExportData {
+        ExportData {
+            title: "Measurements".to_string(),
+            format: ExportFormat::Csv,
+            headers: vec!["Id".to_string()],
+            rows: (0..rows).map(|i| vec![i.to_string()]).collect(),
+            options: Some(ExportOptions {
+                sample,
+                ..Default::default()
+            }),
+            column_metadata: None,
+            summary_row: None,
+            sheets: None,
+        }
+    }
+
+    fn sampled(size: usize, seed: u64) -> ExportData {
+        let mut data = numbered_data(1000, Some(SampleOption { size, seed }));
+        apply_sample(&mut data);
+        data
+    }
+
+    #[test]
+    fn test_same_seed_yields_same_rows() {
+        let first = sampled(50, 42);
+        let second = sampled(50, 42);
+
+        assert_eq!(first.rows.len(), 50);
+        assert_eq!(first.rows, second.rows);
+        // Original order is preserved
+        let ids: Vec<usize> = first.rows.iter().map(|r| r[0].parse().unwrap()).collect();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(
+            first.title,
+            "Measurements (sample of 50 of 1000 rows, seed 42)"
+        );
+    }
+
+    #[test]
+    fn test_different_seed_yields_different_rows() {
+        assert_ne!(sampled(50, 42).rows, sampled(50, 7).rows);
+    }
+
+    #[test]
+    fn test_sample_larger_than_data_keeps_everything() {
+        let mut data = numbered_data(10, Some(SampleOption { size: 20, seed: 1 }));
+        apply_sample(&mut data);
+
+        assert_eq!(data.rows.len(), 10);
+        assert_eq!(data.title, "Measurements");
+    }
+}
@@ -0,0 +1,109 @@
+use super::models::{ExportData, ExportFormat};
+
+/// Fixed bytes every file of the format carries (container, styles, fonts)
+fn format_overhead(format: ExportFormat) -> usize {
+    match format {
+        ExportFormat::Csv | ExportFormat::Tsv => 0,
+        ExportFormat::Json => 2,
+        ExportFormat::Excel => 8 * 1024,
+        ExportFormat::Pdf => 4 * 1024,
+        ExportFormat::Html => 256,
+    }
+}
+
+/// Extra bytes written around each cell (separators, quoting, markup)
+fn per_cell_overhead(format: ExportFormat) -> usize {
+    match format {
+        ExportFormat::Csv | ExportFormat::Tsv => 3,
+        ExportFormat::Json => 6,
+        ExportFormat::Excel => 24,
+        ExportFormat::Pdf => 48,
+        ExportFormat::Html => 36,
+    }
+}
+
+/// Estimate the size in bytes of the file `data` will export to
+///
+/// This is a cheap upper-leaning guess from the cell text and the format's per-cell
+/// overhead, used to reject oversized exports before any output is generated. JSON
+/// repeats the header as the key of every cell, so it is counted once per row.
+/// A multi-sheet export is the sum of its sheets' tables.
+pub fn estimate_output_bytes(data: &ExportData) -> usize {
+    let format = data.format;
+    let tables: usize = match &data.sheets {
+        Some(sheets) => sheets
+            .iter()
+            .map(|sheet| table_bytes(format, &sheet.headers, &sheet.rows, &sheet.summary_row))
+            .sum(),
+        None => table_bytes(format, &data.headers, &data.rows, &data.summary_row),
+    };
+    format_overhead(format) + tables
+}
+
+/// Estimated bytes of one table's header, rows and summary row
+fn table_bytes(
+    format: ExportFormat,
+    headers: &[String],
+    rows: &[Vec<String>],
+    summary_row: &Option<Vec<String>>,
+) -> usize {
+    let per_cell = per_cell_overhead(format);
+    let header_bytes: usize = headers.iter().map(|h| h.len() + per_cell).sum();
+    let row_bytes = |row: &Vec<String>| -> usize {
+        let cells: usize = row.iter().map(|cell| cell.len() + per_cell).sum();
+        match format {
+            ExportFormat::Json => cells + header_bytes,
+            _ => cells,
+        }
+    };
+
+    let body: usize = rows.iter().chain(summary_row.iter()).map(row_bytes).sum();
+    let headers = match format {
+        ExportFormat::Json => 0,
+        _ => header_bytes,
+    };
+    headers + body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(format: ExportFormat, rows: usize) -> ExportData {
+        ExportData {
+            title: "Sizes".to_string(),
+            format,
+            headers: vec!["Name".to_string(), "Qty".to_string()],
+            rows: vec![vec!["Widget".to_string(), "12".to_string()]; rows],
+            options: None,
+            column_metadata: None,
+            summary_row: None,
+            sheets: None,
+        }
+    }
+
+    #[test]
+    fn test_estimate_grows_linearly_with_rows() {
+        for format in [
+            ExportFormat::Csv,
+            ExportFormat::Json,
+            ExportFormat::Excel,
+            ExportFormat::Pdf,
+            ExportFormat::Html,
+        ] {
+            let base = estimate_output_bytes(&data(format, 10));
+            let per_row = estimate_output_bytes(&data(format, 11)) - base;
+            assert!(per_row > 0, "{:?}", format);
+            assert_eq!(estimate_output_bytes(&data(format, 1010)), base + 1000 * per_row);
+        }
+    }
+
+    #[test]
+    fn test_csv_estimate_covers_actual_output() {
+        // "Name,Qty\n" + 3 x "Widget,12\n"
+        let actual = 9 + 3 * 10;
+        let estimate = estimate_output_bytes(&data(ExportFormat::Csv, 3));
+        assert!(estimate >= actual, "{} < {}", estimate, actual);
+        assert!(estimate < actual * 2, "{} too pessimistic", estimate);
+    }
+}
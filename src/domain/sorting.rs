@@ -0,0 +1,106 @@
+use super::models::{ExportData, SortOrder};
+use std::cmp::Ordering;
+
+/// Sort the rows by the `sort_by` column when the option is set
+///
+/// Number, currency and percentage columns compare numerically; cells that do not
+/// parse as numbers go last in either direction. Other columns compare as strings.
+/// The sort is stable, so rows with equal keys keep their relative order.
+pub fn apply_sort(data: &mut ExportData) {
+    let Some(sort) = data.options.as_ref().and_then(|o| o.sort_by.as_ref()) else {
+        return;
+    };
+    let Some(col_idx) = sort.column.resolve(&data.headers) else {
+        return;
+    };
+    let order = sort.order;
+
+    if data.column_type(col_idx).is_right_aligned() {
+        let number = |row: &Vec<String>| cell(row, col_idx).trim().parse::<f64>().ok();
+        data.rows.sort_by(|a, b| match (number(a), number(b)) {
+            (Some(a), Some(b)) => directed(a.total_cmp(&b), order),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        });
+    } else {
+        data.rows
+            .sort_by(|a, b| directed(cell(a, col_idx).cmp(cell(b, col_idx)), order));
+    }
+}
+
+fn cell(row: &[String], col_idx: usize) -> &str {
+    row.get(col_idx).map(String::as_str).unwrap_or("")
+}
+
+fn directed(ordering: Ordering, order: SortOrder) -> Ordering {
+    match order {
+        SortOrder::Asc => ordering,
+        SortOrder::Desc => ordering.reverse(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{
+        ColumnMetadata, ColumnRef, ExportFormat, ExportOptions, SortOption,
+    };
+
+    fn sorted(column_type: ColumnMetadata, values: &[&str], order: SortOrder) -> Vec<String> {
+        let mut data = ExportData {
+            title: "Sorted".to_string(),
+            format: ExportFormat::Csv,
+            headers: vec!["Label".to_string(), "Value".to_string()],
+            rows: values
+                .iter()
+                .enumerate()
+                .map(|(i, v)| vec![format!("row {}", i), v.to_string()])
+                .collect(),
+            options: Some(ExportOptions {
+                sort_by: Some(SortOption {
+                    column: ColumnRef::Name("Value".to_string()),
+                    order,
+                }),
+                ..Default::default()
+            }),
+            column_metadata: Some(vec![ColumnMetadata::text(), column_type]),
+            summary_row: None,
+            sheets: None,
+        };
+        apply_sort(&mut data);
+        data.rows.into_iter().map(|row| row[1].clone()).collect()
+    }
+
+    #[test]
+    fn test_numeric_column_sorts_by_value() {
+        let values = sorted(ColumnMetadata::number(), &["2", "10", "1"], SortOrder::Asc);
+        assert_eq!(values, vec!["1", "2", "10"]);
+
+        let values = sorted(ColumnMetadata::number(), &["2", "10", "1"], SortOrder::Desc);
+        assert_eq!(values, vec!["10", "2", "1"]);
+    }
+
+    #[test]
+    fn test_text_column_sorts_lexicographically() {
+        let values = sorted(ColumnMetadata::text(), &["2", "10", "1"], SortOrder::Asc);
+        assert_eq!(values, vec!["1", "10", "2"]);
+
+        let values = sorted(
+            ColumnMetadata::text(),
+            &["pear", "apple", "fig"],
+            SortOrder::Asc,
+        );
+        assert_eq!(values, vec!["apple", "fig", "pear"]);
+    }
+
+    #[test]
+    fn test_unparseable_numbers_go_last() {
+        let values = sorted(
+            ColumnMetadata::number(),
+            &["n/a", "3", "", "1"],
+            SortOrder::Desc,
+        );
+        assert_eq!(values, vec!["3", "1", "n/a", ""]);
+    }
+}
@@ -0,0 +1,111 @@
+use super::models::{ExportData, SummaryStat};
+
+/// Fill `summary_row` with a sum or mean of each numeric column when `summary_stats` is set
+///
+/// Only number, currency and percentage columns get a value; text and date columns stay
+/// blank, as do numeric columns without any parseable cell. Cells that do not parse as
+/// numbers are skipped. An explicit `summary_row` in the request is kept as sent.
+pub fn apply_summary_stats(data: &mut ExportData) {
+    let Some(stat) = data.options.as_ref().and_then(|o| o.summary_stats) else {
+        return;
+    };
+    if data.summary_row.is_some() {
+        return;
+    }
+
+    let summary = (0..data.headers.len())
+        .map(|col_idx| {
+            if !data.column_type(col_idx).is_right_aligned() {
+                return String::new();
+            }
+            let cells = data.rows.iter().filter_map(|row| row.get(col_idx));
+            column_stat(cells.map(String::as_str), stat).unwrap_or_default()
+        })
+        .collect();
+    data.summary_row = Some(summary);
+}
+
+/// Compute the statistic over the parseable cells, keeping the most decimals seen
+fn column_stat<'a>(cells: impl Iterator<Item = &'a str>, stat: SummaryStat) -> Option<String> {
+    let (mut total, mut count, mut decimals) = (0.0, 0usize, 0usize);
+    for cell in cells {
+        let cell = cell.trim();
+        let Some(value) = cell.parse::<f64>().ok().filter(|v| v.is_finite()) else {
+            continue;
+        };
+        total += value;
+        count += 1;
+        decimals = decimals.max(cell.split_once('.').map_or(0, |(_, frac)| frac.len()));
+    }
+    if count == 0 {
+        return None;
+    }
+
+    Some(match stat {
+        SummaryStat::Sum => format!("{:.*}", decimals, total),
+        SummaryStat::Mean => format!("{:.*}", decimals.max(2), total / count as f64),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{ColumnMetadata, ExportFormat, ExportOptions};
+
+    fn sales(stat: Option<SummaryStat>) -> ExportData {
+        ExportData {
+            title: "Sales".to_string(),
+            format: ExportFormat::Csv,
+            headers: vec!["Item".to_string(), "Qty".to_string(), "Price".to_string()],
+            rows: vec![
+                vec!["Widget".to_string(), "2".to_string(), "5.50".to_string()],
+                vec!["Gadget".to_string(), "n/a".to_string(), "150".to_string()],
+                vec!["Gizmo".to_string(), "4".to_string(), "".to_string()],
+            ],
+            options: Some(ExportOptions {
+                summary_stats: stat,
+                ..Default::default()
+            }),
+            column_metadata: Some(vec![
+                ColumnMetadata::text(),
+                ColumnMetadata::number(),
+                ColumnMetadata::currency(),
+            ]),
+            summary_row: None,
+            sheets: None,
+        }
+    }
+
+    #[test]
+    fn test_sum_skips_unparseable_cells() {
+        let mut data = sales(Some(SummaryStat::Sum));
+        apply_summary_stats(&mut data);
+
+        assert_eq!(data.summary_row.unwrap(), vec!["", "6", "155.50"]);
+    }
+
+    #[test]
+    fn test_mean_of_parseable_cells() {
+        let mut data = sales(Some(SummaryStat::Mean));
+        apply_summary_stats(&mut data);
+
+        assert_eq!(data.summary_row.unwrap(), vec!["", "3.00", "77.75"]);
+    }
+
+    #[test]
+    fn test_explicit_summary_row_is_kept() {
+        let mut data = sales(Some(SummaryStat::Sum));
+        data.summary_row = Some(vec!["Total".to_string(), "".to_string(), "".to_string()]);
+        apply_summary_stats(&mut data);
+
+        assert_eq!(data.summary_row.unwrap()[0], "Total");
+    }
+
+    #[test]
+    fn test_no_summary_without_option() {
+        let mut data = sales(None);
+        apply_summary_stats(&mut data);
+
+        assert!(data.summary_row.is_none());
+    }
+}
@@ -0,0 +1,80 @@
+use super::models::ExportData;
+
+/// Trim surrounding whitespace from data and summary cells when `trim_cells` is set
+///
+/// Columns whose metadata sets `preserve_whitespace` are left untouched. Runs before
+/// type inference so padded numbers such as `" 42 "` are recognised.
+pub fn trim_cells(data: &mut ExportData) {
+    let enabled = data
+        .options
+        .as_ref()
+        .and_then(|o| o.trim_cells)
+        .unwrap_or(false);
+    if !enabled {
+        return;
+    }
+
+    let preserved: Vec<bool> = (0..data.headers.len())
+        .map(|col_idx| {
+            data.column_metadata
+                .as_ref()
+                .and_then(|metadata| metadata.get(col_idx))
+                .is_some_and(|meta| meta.preserve_whitespace)
+        })
+        .collect();
+
+    let summary = data.summary_row.iter_mut();
+    for row in data.rows.iter_mut().chain(summary) {
+        for (col_idx, cell) in row.iter_mut().enumerate() {
+            if preserved.get(col_idx).copied().unwrap_or(false) {
+                continue;
+            }
+            let trimmed = cell.trim();
+            if trimmed.len() != cell.len() {
+                *cell = trimmed.to_string();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{ColumnMetadata, ExportFormat, ExportOptions};
+
+    fn padded_data(trim: Option<bool>) -> ExportData {
+        ExportData {
+            title: "Padded".to_string(),
+            format: ExportFormat::Csv,
+            headers: vec!["Name".to_string(), "Code".to_string()],
+            rows: vec![vec![" Ann\t".to_string(), "  007 ".to_string()]],
+            options: Some(ExportOptions {
+                trim_cells: trim,
+                ..Default::default()
+            }),
+            column_metadata: Some(vec![
+                ColumnMetadata::text(),
+                ColumnMetadata::text().with_preserve_whitespace(),
+            ]),
+            summary_row: Some(vec![" Total ".to_string(), " 1 ".to_string()]),
+            sheets: None,
+        }
+    }
+
+    #[test]
+    fn test_trims_all_but_preserved_columns() {
+        let mut data = padded_data(Some(true));
+        trim_cells(&mut data);
+
+        assert_eq!(data.rows[0], vec!["Ann", "  007 "]);
+        assert_eq!(data.summary_row.unwrap(), vec!["Total", " 1 "]);
+    }
+
+    #[test]
+    fn test_leaves_cells_alone_by_default() {
+        let mut data = padded_data(None);
+        trim_cells(&mut data);
+
+        assert_eq!(data.rows[0], vec![" Ann\t", "  007 "]);
+    }
+}
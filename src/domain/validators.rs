@@ -1,33 +1,215 @@
-use super::models::ExportData;
+use std::collections::HashSet;
+use super::models::{ColumnMetadata, ColumnType, ExportData, ExportFormat, Sheet, TableView};
 use super::errors::DomainError;
+use super::locale::Locale;
+use super::dates::is_valid_format;
+
+/// Longest accepted `ellipsis` option, in characters
+const MAX_ELLIPSIS_CHARS: usize = 3;
+
+/// Longest worksheet name Excel accepts, in characters
+const MAX_SHEET_TITLE_CHARS: usize = 31;
+
+/// Characters Excel does not allow in a worksheet name
+const SHEET_TITLE_FORBIDDEN: [char; 7] = ['[', ']', ':', '*', '?', '/', '\\'];
 
 /// Validator trait
 pub trait ExportValidator: Send + Sync {
     fn validate(&self, data: &ExportData) -> Result<(), DomainError>;
+
+    /// Fix up data the validator is configured to accept rather than reject;
+    /// runs before `validate` and returns a warning for each lossy fix
+    fn repair(&self, _data: &mut ExportData) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// What to do with cells longer than `max_cell_length`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum OverlongCellMode {
+    /// Fail validation (strict)
+    #[default]
+    Reject,
+    /// Cut the cell down to the limit and report a warning
+    Truncate,
+}
+
+impl std::str::FromStr for OverlongCellMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "reject" => Ok(Self::Reject),
+            "truncate" => Ok(Self::Truncate),
+            other => Err(format!("Unknown overlong cell mode: {}", other)),
+        }
+    }
+}
+
+/// What to do when `column_metadata` has fewer entries than there are headers
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ShortMetadataMode {
+    /// Treat the remaining columns as `ColumnMetadata::text()`, or leave them to type
+    /// inference when the request sets `infer_types`
+    #[default]
+    Pad,
+    /// Fail validation
+    Strict,
+}
+
+impl std::str::FromStr for ShortMetadataMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "pad" => Ok(Self::Pad),
+            "strict" => Ok(Self::Strict),
+            other => Err(format!("Unknown short metadata mode: {}", other)),
+        }
+    }
+}
+
+/// What to do with data rows whose length differs from the header count
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum JaggedRowPolicy {
+    /// Fail validation with `ColumnCountMismatch` (strict)
+    #[default]
+    Reject,
+    /// Append empty cells to short rows; long rows are still rejected
+    PadWithEmpty,
+    /// Drop the extra cells of long rows; short rows are still rejected
+    Truncate,
+}
+
+impl std::str::FromStr for JaggedRowPolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "reject" => Ok(Self::Reject),
+            "pad" => Ok(Self::PadWithEmpty),
+            "truncate" => Ok(Self::Truncate),
+            other => Err(format!("Unknown jagged row policy: {}", other)),
+        }
+    }
+}
+
+/// Size limits enforced by the default validator
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValidationLimits {
+    pub max_rows: usize,
+    pub max_columns: usize,
+    pub max_cell_length: usize,
+    pub max_header_length: usize,
+    pub overlong_cells: OverlongCellMode,
+    /// Accept exports with headers but no data rows
+    pub allow_empty_rows: bool,
+    /// Pad or reject `column_metadata` shorter than the headers
+    pub short_metadata: ShortMetadataMode,
+    /// Pad, truncate or reject data rows that do not match the header count
+    pub jagged_rows: JaggedRowPolicy,
+}
+
+impl Default for ValidationLimits {
+    fn default() -> Self {
+        Self {
+            max_rows: 10000,
+            max_columns: 256,
+            max_cell_length: 1000,
+            max_header_length: 1000,
+            overlong_cells: OverlongCellMode::Reject,
+            allow_empty_rows: false,
+            short_metadata: ShortMetadataMode::Pad,
+            jagged_rows: JaggedRowPolicy::Reject,
+        }
+    }
 }
 
 /// Default validator implementation
-pub struct DefaultExportValidator;
+#[derive(Debug, Default)]
+pub struct DefaultExportValidator {
+    limits: ValidationLimits,
+}
 
-impl ExportValidator for DefaultExportValidator {
-    fn validate(&self, data: &ExportData) -> Result<(), DomainError> {
+impl DefaultExportValidator {
+    pub fn new(limits: ValidationLimits) -> Self {
+        Self { limits }
+    }
+
+    /// Validator with the given size limits and the default modes
+    pub fn with_limits(max_rows: usize, max_cell_length: usize, max_header_length: usize) -> Self {
+        Self::new(ValidationLimits {
+            max_rows,
+            max_cell_length,
+            max_header_length,
+            ..ValidationLimits::default()
+        })
+    }
+
+    /// Get the configured limits
+    pub fn limits(&self) -> &ValidationLimits {
+        &self.limits
+    }
+
+    /// Check that the sheet titles are usable, distinct Excel worksheet names
+    fn validate_sheet_titles(sheets: &[Sheet]) -> Result<(), DomainError> {
+        // Excel compares worksheet names case-insensitively
+        let mut seen = HashSet::new();
+        for sheet in sheets {
+            let title = &sheet.title;
+            let problem = if title.is_empty() {
+                Some("cannot be empty".to_string())
+            } else if title.chars().count() > MAX_SHEET_TITLE_CHARS {
+                Some(format!("must be at most {} characters", MAX_SHEET_TITLE_CHARS))
+            } else if title.contains(SHEET_TITLE_FORBIDDEN) {
+                Some("cannot contain any of [ ] : * ? / \\".to_string())
+            } else if title.starts_with('\'') || title.ends_with('\'') {
+                Some("cannot start or end with an apostrophe".to_string())
+            } else if !seen.insert(title.to_lowercase()) {
+                Some("is used by more than one sheet".to_string())
+            } else {
+                None
+            };
+            if let Some(problem) = problem {
+                return Err(DomainError::InvalidOption(format!(
+                    "Sheet title {:?} {}",
+                    title, problem
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check one table: the top-level one, or a worksheet with the export's options
+    fn validate_table(&self, data: TableView<'_>) -> Result<(), DomainError> {
         // Check headers
         if data.headers.is_empty() {
             return Err(DomainError::EmptyData("Headers cannot be empty".to_string()));
         }
 
         // Check rows
-        if data.rows.is_empty() {
+        if data.rows.is_empty() && !self.limits.allow_empty_rows {
             return Err(DomainError::EmptyData("Data rows cannot be empty".to_string()));
         }
 
         // Check row count limit
-        if data.rows.len() > 10000 {
-            return Err(DomainError::TooManyRows(data.rows.len()));
+        if data.rows.len() > self.limits.max_rows {
+            return Err(DomainError::TooManyRows {
+                count: data.rows.len(),
+                max: self.limits.max_rows,
+            });
         }
 
         let header_count = data.headers.len();
 
+        // Check column count limit
+        if header_count > self.limits.max_columns {
+            return Err(DomainError::TooManyColumns {
+                count: header_count,
+                max: self.limits.max_columns,
+            });
+        }
+
         // Validate each row
         for (i, row) in data.rows.iter().enumerate() {
             // Column count match
@@ -41,19 +223,675 @@ impl ExportValidator for DefaultExportValidator {
 
             // Cell length check
             for cell in row.iter() {
-                if cell.len() > 1000 {
+                if cell.len() > self.limits.max_cell_length {
+                    return Err(DomainError::CellTooLong(cell.len()));
+                }
+            }
+        }
+
+        // QR code columns need a value to encode in every data row
+        for col_idx in 0..header_count {
+            if data.column_type(col_idx) != ColumnType::QrCode {
+                continue;
+            }
+            if let Some(row) = data.rows.iter().position(|row| row[col_idx].trim().is_empty()) {
+                return Err(DomainError::EmptyData(format!(
+                    "Row {}: QR code column '{}' cannot be empty",
+                    row + 1,
+                    data.headers[col_idx]
+                )));
+            }
+        }
+
+        // Metadata repair pads short metadata unless strict mode is configured
+        if let Some(metadata) = data.column_metadata {
+            let strict = self.limits.short_metadata == ShortMetadataMode::Strict;
+            if strict && metadata.len() < header_count {
+                return Err(DomainError::MetadataColumnCountMismatch {
+                    expected: header_count,
+                    actual: metadata.len(),
+                });
+            }
+        }
+
+        // Summary row follows the same shape and length rules as data rows
+        if let Some(summary) = data.summary_row {
+            if summary.len() != header_count {
+                return Err(DomainError::SummaryColumnCountMismatch {
+                    expected: header_count,
+                    actual: summary.len(),
+                });
+            }
+            for cell in summary {
+                if cell.len() > self.limits.max_cell_length {
                     return Err(DomainError::CellTooLong(cell.len()));
                 }
             }
         }
 
         // Check header length
-        for header in &data.headers {
-            if header.len() > 1000 {
+        for header in data.headers {
+            if header.len() > self.limits.max_header_length {
                 return Err(DomainError::CellTooLong(header.len()));
             }
         }
 
+        if let Some(options) = data.options {
+            // Check locale is supported
+            if let Some(tag) = options.locale.as_deref() {
+                if Locale::from_tag(tag).is_none() {
+                    return Err(DomainError::InvalidOption(format!("Unsupported locale: {}", tag)));
+                }
+            }
+
+            // Check date formats are usable chrono format strings
+            let date_formats = [
+                ("date_input_format", &options.date_input_format),
+                ("date_output_format", &options.date_output_format),
+            ];
+            for (name, format) in date_formats {
+                if format.as_deref().is_some_and(|format| !is_valid_format(format)) {
+                    return Err(DomainError::InvalidOption(format!(
+                        "{} is not a valid date format",
+                        name
+                    )));
+                }
+            }
+
+            // Check ellipsis is short enough to leave room for the cell text
+            if let Some(ellipsis) = options.ellipsis.as_deref() {
+                if ellipsis.chars().count() > MAX_ELLIPSIS_CHARS {
+                    return Err(DomainError::InvalidOption(format!(
+                        "ellipsis must be at most {} characters",
+                        MAX_ELLIPSIS_CHARS
+                    )));
+                }
+            }
+
+            // Check sample size
+            if options.sample.as_ref().is_some_and(|sample| sample.size == 0) {
+                return Err(DomainError::InvalidOption(
+                    "sample size must be at least 1".to_string(),
+                ));
+            }
+
+            // Check sort column
+            if let Some(sort) = &options.sort_by {
+                if sort.column.resolve(data.headers).is_none() {
+                    return Err(DomainError::InvalidOption(format!(
+                        "Unknown sort_by column: {:?}",
+                        sort.column
+                    )));
+                }
+            }
+
+            // Check filter column
+            if let Some(filter) = &options.filter {
+                if filter.column.resolve(data.headers).is_none() {
+                    return Err(DomainError::InvalidOption(format!(
+                        "Unknown filter column: {:?}",
+                        filter.column
+                    )));
+                }
+            }
+
+            // Check group column
+            if let Some(column) = &options.group_by {
+                if column.resolve(data.headers).is_none() {
+                    return Err(DomainError::InvalidOption(format!(
+                        "Unknown group_by column: {:?}",
+                        column
+                    )));
+                }
+            }
+
+            // The CSV writer takes a single-byte delimiter
+            if let Some(delimiter) = &options.delimiter {
+                if delimiter.len() != 1 {
+                    return Err(DomainError::InvalidOption(format!(
+                        "delimiter must be a single ASCII character, got {:?}",
+                        delimiter
+                    )));
+                }
+            }
+
+            // Check bullet list column and delimiter
+            if let Some(bullets) = &options.bullet_list {
+                if bullets.column.resolve(data.headers).is_none() {
+                    return Err(DomainError::InvalidOption(format!(
+                        "Unknown bullet_list column: {:?}",
+                        bullets.column
+                    )));
+                }
+                if bullets.delimiter.is_empty() {
+                    return Err(DomainError::InvalidOption(
+                        "bullet_list delimiter cannot be empty".to_string(),
+                    ));
+                }
+            }
+        }
+
         Ok(())
     }
 }
+
+impl ExportValidator for DefaultExportValidator {
+    fn repair(&self, data: &mut ExportData) -> Vec<String> {
+        if let Some(sheets) = data.sheets.take() {
+            let mut warnings = Vec::new();
+            let sheets = sheets
+                .into_iter()
+                .map(|sheet| {
+                    let title = sheet.title.clone();
+                    let mut sheet_data = data.for_sheet(sheet);
+                    for warning in self.repair(&mut sheet_data) {
+                        warnings.push(format!("sheet '{}': {}", title, warning));
+                    }
+                    Sheet::from(sheet_data)
+                })
+                .collect();
+            data.sheets = Some(sheets);
+            return warnings;
+        }
+
+        let width = data.headers.len();
+        for row in &mut data.rows {
+            match self.limits.jagged_rows {
+                JaggedRowPolicy::PadWithEmpty if row.len() < width => {
+                    row.resize(width, String::new())
+                }
+                JaggedRowPolicy::Truncate if row.len() > width => row.truncate(width),
+                _ => {}
+            }
+        }
+
+        // With `infer_types` the missing columns are left for type inference to fill
+        let infers_types = data
+            .options
+            .as_ref()
+            .and_then(|o| o.infer_types)
+            .unwrap_or(false);
+        if self.limits.short_metadata == ShortMetadataMode::Pad && !infers_types {
+            if let Some(metadata) = &mut data.column_metadata {
+                if metadata.len() < data.headers.len() {
+                    metadata.resize_with(data.headers.len(), ColumnMetadata::text);
+                }
+            }
+        }
+
+        if self.limits.overlong_cells != OverlongCellMode::Truncate {
+            return Vec::new();
+        }
+
+        let max = self.limits.max_cell_length;
+        let mut warnings = Vec::new();
+        let rows = data.rows.iter_mut().enumerate().map(|(i, row)| (format!("row {}", i + 1), row));
+        let summary = data.summary_row.iter_mut().map(|row| ("summary row".to_string(), row));
+        for (label, row) in rows.chain(summary) {
+            for cell in row.iter_mut().filter(|cell| cell.len() > max) {
+                warnings.push(format!(
+                    "truncated {}-byte cell in {} to {} bytes",
+                    cell.len(),
+                    label,
+                    max
+                ));
+                let mut end = max;
+                while !cell.is_char_boundary(end) {
+                    end -= 1;
+                }
+                cell.truncate(end);
+            }
+        }
+        warnings
+    }
+
+    fn validate(&self, data: &ExportData) -> Result<(), DomainError> {
+        // Each worksheet is checked as a table of its own; the top-level table is unused
+        if let Some(sheets) = &data.sheets {
+            if data.format != ExportFormat::Excel {
+                return Err(DomainError::InvalidOption(
+                    "sheets are only supported for Excel exports".to_string(),
+                ));
+            }
+            if sheets.is_empty() {
+                return Err(DomainError::EmptyData("Sheets cannot be empty".to_string()));
+            }
+            // The row limit covers the whole workbook, not each sheet
+            let total_rows: usize = sheets.iter().map(|sheet| sheet.rows.len()).sum();
+            if total_rows > self.limits.max_rows {
+                return Err(DomainError::TooManyRows {
+                    count: total_rows,
+                    max: self.limits.max_rows,
+                });
+            }
+            Self::validate_sheet_titles(sheets)?;
+            for sheet in sheets {
+                self.validate_table(data.sheet_table(sheet))?;
+            }
+            return Ok(());
+        }
+
+        self.validate_table(data.table())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{
+        ColumnMetadata, ColumnRef, ExportFormat, ExportOptions, FilterOp, FilterOption, SortOption,
+    };
+
+    fn sample_data(options: Option<ExportOptions>) -> ExportData {
+        ExportData {
+            title: "Report".to_string(),
+            format: ExportFormat::Pdf,
+            headers: vec!["Name".to_string()],
+            rows: vec![vec!["Widget".to_string()]],
+            options,
+            column_metadata: None,
+            summary_row: None,
+            sheets: None,
+        }
+    }
+
+    #[test]
+    fn test_ellipsis_length_is_limited() {
+        let with_ellipsis = |ellipsis: &str| {
+            sample_data(Some(ExportOptions {
+                ellipsis: Some(ellipsis.to_string()),
+                ..Default::default()
+            }))
+        };
+        let validator = DefaultExportValidator::default();
+
+        assert!(validator.validate(&with_ellipsis("\u{2026}")).is_ok());
+        assert!(validator.validate(&with_ellipsis("...")).is_ok());
+        assert!(matches!(
+            validator.validate(&with_ellipsis("....")),
+            Err(DomainError::InvalidOption(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_date_format_is_rejected() {
+        let data = sample_data(Some(ExportOptions {
+            date_output_format: Some("%d/%Q".to_string()),
+            ..Default::default()
+        }));
+
+        let err = DefaultExportValidator::default().validate(&data).unwrap_err();
+        assert!(matches!(err, DomainError::InvalidOption(_)));
+        assert!(err.to_string().contains("date_output_format"), "{}", err);
+    }
+
+    #[test]
+    fn test_summary_row_must_match_header_count() {
+        let validator = DefaultExportValidator::default();
+
+        for format in [ExportFormat::Excel, ExportFormat::Csv, ExportFormat::Pdf] {
+            let summary = |cells: &[&str]| ExportData {
+                format,
+                summary_row: Some(cells.iter().map(|cell| cell.to_string()).collect()),
+                ..sample_data(None)
+            };
+
+            assert!(validator.validate(&summary(&["Total"])).is_ok());
+            assert!(matches!(
+                validator.validate(&summary(&["Total", "3"])),
+                Err(DomainError::SummaryColumnCountMismatch { expected: 1, actual: 2 })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_row_limit_from_with_limits() {
+        let validator = DefaultExportValidator::with_limits(2, 1000, 1000);
+        let data = |rows: usize| ExportData {
+            rows: vec![vec!["Widget".to_string()]; rows],
+            ..sample_data(None)
+        };
+
+        assert!(validator.validate(&data(2)).is_ok());
+        assert!(matches!(
+            validator.validate(&data(3)),
+            Err(DomainError::TooManyRows { count: 3, max: 2 })
+        ));
+        assert_eq!(
+            DefaultExportValidator::with_limits(10000, 1000, 1000).limits(),
+            DefaultExportValidator::default().limits()
+        );
+    }
+
+    #[test]
+    fn test_overlong_cell_rejected_by_default() {
+        let mut data = sample_data(None);
+        data.rows[0][0] = "x".repeat(1001);
+        let validator = DefaultExportValidator::default();
+
+        validator.repair(&mut data);
+        assert_eq!(data.rows[0][0].len(), 1001);
+        assert!(matches!(
+            validator.validate(&data),
+            Err(DomainError::CellTooLong(1001))
+        ));
+    }
+
+    #[test]
+    fn test_overlong_cell_truncated_in_truncate_mode() {
+        let mut data = sample_data(None);
+        data.rows[0][0] = "x".repeat(1001);
+        data.summary_row = Some(vec!["\u{0e01}".repeat(400)]);
+        let validator = DefaultExportValidator::new(ValidationLimits {
+            overlong_cells: OverlongCellMode::Truncate,
+            ..Default::default()
+        });
+
+        let warnings = validator.repair(&mut data);
+        assert_eq!(
+            warnings,
+            vec![
+                "truncated 1001-byte cell in row 1 to 1000 bytes",
+                "truncated 1200-byte cell in summary row to 1000 bytes",
+            ]
+        );
+        assert_eq!(data.rows[0][0], "x".repeat(1000));
+        // Three-byte characters are cut on a character boundary
+        assert_eq!(data.summary_row.as_ref().unwrap()[0].len(), 999);
+        assert!(validator.validate(&data).is_ok());
+    }
+
+    #[test]
+    fn test_empty_rows_allowed_when_configured() {
+        let data = ExportData {
+            rows: Vec::new(),
+            ..sample_data(None)
+        };
+        let lenient = DefaultExportValidator::new(ValidationLimits {
+            allow_empty_rows: true,
+            ..Default::default()
+        });
+
+        assert!(matches!(
+            DefaultExportValidator::default().validate(&data),
+            Err(DomainError::EmptyData(_))
+        ));
+        assert!(lenient.validate(&data).is_ok());
+    }
+
+    #[test]
+    fn test_column_count_limit() {
+        let validator = DefaultExportValidator::new(ValidationLimits {
+            max_columns: 3,
+            ..Default::default()
+        });
+        let with_columns = |count: usize| ExportData {
+            headers: (0..count).map(|i| format!("Col {}", i)).collect(),
+            rows: vec![vec!["x".to_string(); count]],
+            ..sample_data(None)
+        };
+
+        assert!(validator.validate(&with_columns(3)).is_ok());
+        assert!(matches!(
+            validator.validate(&with_columns(4)),
+            Err(DomainError::TooManyColumns { count: 4, max: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_sort_column_must_exist() {
+        let sort_by = |column: ColumnRef| {
+            sample_data(Some(ExportOptions {
+                sort_by: Some(SortOption {
+                    column,
+                    order: Default::default(),
+                }),
+                ..Default::default()
+            }))
+        };
+        let validator = DefaultExportValidator::default();
+
+        assert!(validator.validate(&sort_by(ColumnRef::Name("Name".to_string()))).is_ok());
+        assert!(matches!(
+            validator.validate(&sort_by(ColumnRef::Index(3))),
+            Err(DomainError::InvalidOption(_))
+        ));
+    }
+
+    #[test]
+    fn test_filter_column_must_exist() {
+        let filter = |column: ColumnRef| {
+            sample_data(Some(ExportOptions {
+                filter: Some(FilterOption {
+                    column,
+                    op: FilterOp::Eq,
+                    value: "x".to_string(),
+                }),
+                ..Default::default()
+            }))
+        };
+        let validator = DefaultExportValidator::default();
+
+        assert!(validator.validate(&filter(ColumnRef::Index(0))).is_ok());
+        assert!(matches!(
+            validator.validate(&filter(ColumnRef::Name("Missing".to_string()))),
+            Err(DomainError::InvalidOption(_))
+        ));
+    }
+
+    #[test]
+    fn test_qr_code_cells_cannot_be_empty() {
+        let mut data = sample_data(None);
+        data.column_metadata = Some(vec![ColumnMetadata::qr_code()]);
+        let validator = DefaultExportValidator::default();
+
+        assert!(validator.validate(&data).is_ok());
+        data.rows.push(vec!["  ".to_string()]);
+        let err = validator.validate(&data).unwrap_err();
+        assert!(matches!(err, DomainError::EmptyData(_)));
+        assert!(err.to_string().contains("Row 2"), "{}", err);
+    }
+
+    #[test]
+    fn test_delimiter_must_be_single_byte() {
+        let with_delimiter = |delimiter: &str| {
+            sample_data(Some(ExportOptions {
+                delimiter: Some(delimiter.to_string()),
+                ..Default::default()
+            }))
+        };
+        let validator = DefaultExportValidator::default();
+
+        assert!(validator.validate(&with_delimiter(";")).is_ok());
+        for delimiter in ["", ";;", "\u{a7}"] {
+            assert!(matches!(
+                validator.validate(&with_delimiter(delimiter)),
+                Err(DomainError::InvalidOption(_))
+            ));
+        }
+    }
+
+    fn short_metadata_data() -> ExportData {
+        ExportData {
+            headers: vec!["Name".to_string(), "Qty".to_string(), "Price".to_string()],
+            rows: vec![vec!["Widget".to_string(), "2".to_string(), "9.99".to_string()]],
+            column_metadata: Some(vec![ColumnMetadata::text()]),
+            ..sample_data(None)
+        }
+    }
+
+    #[test]
+    fn test_jagged_rows_follow_policy() {
+        let data = || ExportData {
+            headers: vec!["Name".to_string(), "Qty".to_string()],
+            rows: vec![
+                vec!["Widget".to_string()],
+                vec!["Gadget".to_string(), "2".to_string(), "extra".to_string()],
+            ],
+            ..sample_data(None)
+        };
+        let validator = |jagged_rows| {
+            DefaultExportValidator::new(ValidationLimits {
+                jagged_rows,
+                ..Default::default()
+            })
+        };
+
+        let strict = validator(JaggedRowPolicy::Reject);
+        let mut rejected = data();
+        strict.repair(&mut rejected);
+        assert_eq!(rejected.rows, data().rows);
+        assert!(matches!(
+            strict.validate(&rejected),
+            Err(DomainError::ColumnCountMismatch { row: 1, expected: 2, actual: 1 })
+        ));
+
+        let padding = validator(JaggedRowPolicy::PadWithEmpty);
+        let mut padded = data();
+        padding.repair(&mut padded);
+        assert_eq!(padded.rows[0], vec!["Widget", ""]);
+        // Long rows are left for validation to reject
+        assert_eq!(padded.rows[1].len(), 3);
+        assert!(matches!(
+            padding.validate(&padded),
+            Err(DomainError::ColumnCountMismatch { row: 2, expected: 2, actual: 3 })
+        ));
+
+        let truncating = validator(JaggedRowPolicy::Truncate);
+        let mut truncated = data();
+        truncated.rows.remove(0);
+        truncating.repair(&mut truncated);
+        assert_eq!(truncated.rows, vec![vec!["Gadget", "2"]]);
+        assert!(truncating.validate(&truncated).is_ok());
+    }
+
+    #[test]
+    fn test_short_metadata_padded_with_text_columns() {
+        let mut data = short_metadata_data();
+        let validator = DefaultExportValidator::default();
+
+        validator.repair(&mut data);
+        let metadata = data.column_metadata.as_ref().unwrap();
+        assert_eq!(metadata.len(), 3);
+        assert!(metadata.iter().all(|m| m.column_type == ColumnType::Text));
+        assert!(validator.validate(&data).is_ok());
+    }
+
+    #[test]
+    fn test_short_metadata_rejected_in_strict_mode() {
+        let mut data = short_metadata_data();
+        let validator = DefaultExportValidator::new(ValidationLimits {
+            short_metadata: ShortMetadataMode::Strict,
+            ..Default::default()
+        });
+
+        validator.repair(&mut data);
+        assert_eq!(data.column_metadata.as_ref().unwrap().len(), 1);
+        assert!(matches!(
+            validator.validate(&data),
+            Err(DomainError::MetadataColumnCountMismatch { expected: 3, actual: 1 })
+        ));
+    }
+
+    fn sheets_data(format: ExportFormat, rows: Vec<Vec<String>>) -> ExportData {
+        let sheet = Sheet {
+            title: "Orders".to_string(),
+            headers: vec!["Order".to_string(), "Qty".to_string()],
+            rows,
+            column_metadata: None,
+            summary_row: None,
+        };
+        ExportData {
+            format,
+            headers: Vec::new(),
+            rows: Vec::new(),
+            sheets: Some(vec![sheet]),
+            ..sample_data(None)
+        }
+    }
+
+    #[test]
+    fn test_each_sheet_is_validated_instead_of_the_top_level_table() {
+        let validator = DefaultExportValidator::default();
+        let row = |cells: &[&str]| cells.iter().map(|cell| cell.to_string()).collect();
+
+        let valid = sheets_data(ExportFormat::Excel, vec![row(&["A-1", "2"])]);
+        assert!(validator.validate(&valid).is_ok());
+        let ragged = sheets_data(ExportFormat::Excel, vec![row(&["A-1"])]);
+        assert!(matches!(
+            validator.validate(&ragged),
+            Err(DomainError::ColumnCountMismatch { row: 1, expected: 2, actual: 1 })
+        ));
+        let no_sheets = ExportData {
+            sheets: Some(Vec::new()),
+            ..valid.clone()
+        };
+        assert!(matches!(validator.validate(&no_sheets), Err(DomainError::EmptyData(_))));
+    }
+
+    #[test]
+    fn test_row_limit_counts_rows_across_sheets() {
+        let validator = DefaultExportValidator::with_limits(3, 1000, 100);
+        let row = || vec!["A-1".to_string(), "2".to_string()];
+        let mut data = sheets_data(ExportFormat::Excel, vec![row(), row()]);
+        assert!(validator.validate(&data).is_ok());
+
+        // Two sheets of two rows each fit the limit separately but not together
+        let second = data.sheets.as_ref().unwrap()[0].clone();
+        data.sheets.as_mut().unwrap().push(Sheet {
+            title: "Returns".to_string(),
+            ..second
+        });
+        assert!(matches!(
+            validator.validate(&data),
+            Err(DomainError::TooManyRows { count: 4, max: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_sheets_are_excel_only() {
+        let row = vec!["A-1".to_string(), "2".to_string()];
+        let data = sheets_data(ExportFormat::Csv, vec![row]);
+
+        assert!(matches!(
+            DefaultExportValidator::default().validate(&data),
+            Err(DomainError::InvalidOption(_))
+        ));
+    }
+
+    #[test]
+    fn test_sheet_titles_must_be_valid_worksheet_names() {
+        let validator = DefaultExportValidator::default();
+        let row = vec!["A-1".to_string(), "2".to_string()];
+        let with_titles = |titles: &[&str]| {
+            let data = sheets_data(ExportFormat::Excel, vec![row.clone()]);
+            let sheet = data.sheets.as_ref().unwrap()[0].clone();
+            let sheets = titles
+                .iter()
+                .map(|title| Sheet {
+                    title: title.to_string(),
+                    ..sheet.clone()
+                })
+                .collect();
+            ExportData {
+                sheets: Some(sheets),
+                ..data
+            }
+        };
+
+        assert!(validator.validate(&with_titles(&["Orders", "Returns"])).is_ok());
+        assert!(validator.validate(&with_titles(&[&"x".repeat(31)])).is_ok());
+        let invalid = [
+            vec![""],
+            vec!["This sheet title is far too long"],
+            vec!["Q1/Q2"],
+            vec!["[Draft]"],
+            vec!["'Quoted'"],
+            vec!["Orders", "orders"],
+        ];
+        for titles in invalid {
+            let result = validator.validate(&with_titles(&titles));
+            assert!(matches!(result, Err(DomainError::InvalidOption(_))), "{:?}", titles);
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use std::io::{Cursor, Write};
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+/// Wrap `bytes` in a ZIP archive holding a single deflated entry called `name`
+pub fn zip_single_file(name: &str, bytes: &[u8]) -> zip::result::ZipResult<Vec<u8>> {
+    zip_files(&[(name, bytes)])
+}
+
+/// Build a ZIP archive of deflated `(name, bytes)` entries, in order
+pub fn zip_files(entries: &[(&str, &[u8])]) -> zip::result::ZipResult<Vec<u8>> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, bytes) in entries {
+        writer.start_file(
+            *name,
+            FileOptions::default().compression_method(zip::CompressionMethod::Deflated),
+        )?;
+        writer.write_all(bytes)?;
+    }
+    Ok(writer.finish()?.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_single_entry_round_trips() {
+        let bytes = zip_single_file("report.csv", b"Name\nWidget\n").unwrap();
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        assert_eq!(archive.len(), 1);
+        let mut entry = archive.by_index(0).unwrap();
+        assert_eq!(entry.name(), "report.csv");
+        let mut content = String::new();
+        entry.read_to_string(&mut content).unwrap();
+        assert_eq!(content, "Name\nWidget\n");
+    }
+}
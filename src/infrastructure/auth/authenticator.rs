@@ -0,0 +1,129 @@
+use axum::http::{header, HeaderMap, HeaderName};
+use sha2::{Digest, Sha256};
+use super::jwt_handler::JwtHandler;
+use crate::application::ports::{Authenticator, Principal};
+use crate::domain::errors::DomainError;
+
+/// Header carrying the key for `ApiKeyAuthenticator`
+pub const API_KEY_HEADER: HeaderName = HeaderName::from_static("x-api-key");
+
+/// Authentication scheme used by `auth_middleware`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AuthScheme {
+    /// `Authorization: Bearer <jwt>` (default)
+    #[default]
+    Jwt,
+    /// `X-API-Key: <key>` compared against a configured static key
+    ApiKey,
+}
+
+impl std::str::FromStr for AuthScheme {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "jwt" => Ok(Self::Jwt),
+            "api_key" => Ok(Self::ApiKey),
+            other => Err(format!("Unknown auth scheme: {}", other)),
+        }
+    }
+}
+
+impl Authenticator for JwtHandler {
+    fn authenticate(&self, headers: &HeaderMap) -> Result<Principal, DomainError> {
+        let token = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|h| h.to_str().ok())
+            .and_then(|auth_header| auth_header.strip_prefix("Bearer "))
+            .ok_or(DomainError::MissingCredentials)?;
+        let claims = self
+            .validate_token(token)
+            .map_err(|_| DomainError::InvalidToken)?;
+        Ok(Principal { subject: claims.sub })
+    }
+}
+
+/// Authenticates requests carrying a static key in `X-API-Key`
+pub struct ApiKeyAuthenticator {
+    key_digest: [u8; 32],
+}
+
+impl ApiKeyAuthenticator {
+    /// Subject of every principal authenticated by API key
+    pub const SUBJECT: &'static str = "api-key";
+
+    pub fn new(key: &str) -> Self {
+        Self {
+            key_digest: Sha256::digest(key.as_bytes()).into(),
+        }
+    }
+}
+
+impl Authenticator for ApiKeyAuthenticator {
+    fn authenticate(&self, headers: &HeaderMap) -> Result<Principal, DomainError> {
+        let key = headers
+            .get(API_KEY_HEADER)
+            .ok_or(DomainError::MissingCredentials)?;
+        // Comparing fixed-size digests in constant time leaks neither content nor length
+        let digest: [u8; 32] = Sha256::digest(key.as_bytes()).into();
+        let difference = digest
+            .iter()
+            .zip(&self.key_digest)
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b));
+        if difference != 0 {
+            return Err(DomainError::InvalidToken);
+        }
+        Ok(Principal {
+            subject: Self::SUBJECT.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(name: HeaderName, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_api_key_authenticates_matching_key_only() {
+        let authenticator = ApiKeyAuthenticator::new("s3cret-key");
+
+        let principal = authenticator
+            .authenticate(&headers(API_KEY_HEADER, "s3cret-key"))
+            .unwrap();
+        assert_eq!(principal.subject, ApiKeyAuthenticator::SUBJECT);
+        assert!(matches!(
+            authenticator.authenticate(&headers(API_KEY_HEADER, "s3cret-kez")),
+            Err(DomainError::InvalidToken)
+        ));
+        assert!(matches!(
+            authenticator.authenticate(&HeaderMap::new()),
+            Err(DomainError::MissingCredentials)
+        ));
+    }
+
+    #[test]
+    fn test_jwt_authenticator_reads_bearer_token() {
+        let handler = JwtHandler::new("secret".to_string(), 60);
+        let token = handler.generate_token().unwrap();
+
+        let bearer = format!("Bearer {}", token);
+        let principal = handler
+            .authenticate(&headers(header::AUTHORIZATION, &bearer))
+            .unwrap();
+        assert_eq!(principal.subject, "web-client");
+        assert!(matches!(
+            handler.authenticate(&headers(header::AUTHORIZATION, "Bearer nope")),
+            Err(DomainError::InvalidToken)
+        ));
+        assert!(matches!(
+            handler.authenticate(&headers(API_KEY_HEADER, &token)),
+            Err(DomainError::MissingCredentials)
+        ));
+    }
+}
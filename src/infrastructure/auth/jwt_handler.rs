@@ -1,25 +1,56 @@
 use jsonwebtoken::{encode, decode, Header, EncodingKey, DecodingKey, Validation};
 use serde::{Deserialize, Serialize};
 use chrono::Utc;
+use crate::domain::errors::DomainError;
 
 /// JWT Claims
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     pub iss: String,      // Issuer
     pub sub: String,      // Subject
     pub exp: i64,         // Expiration
     pub iat: i64,         // Issued at
+    #[serde(default)]
+    pub jti: String,      // Unique token ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>, // Audience
 }
 
 /// JWT Handler
 pub struct JwtHandler {
     secret: String,
     expiration: i64,
+    audience: Option<String>,
+    /// Configured issuer; when set it is also required on validation
+    issuer: Option<String>,
 }
 
+/// Issuer put in tokens when none is configured
+const DEFAULT_ISSUER: &str = "export-service";
+
+/// Subject of tokens issued without one, e.g. by `GET /token`
+pub const DEFAULT_SUBJECT: &str = "web-client";
+
 impl JwtHandler {
     pub fn new(secret: String, expiration: i64) -> Self {
-        Self { secret, expiration }
+        Self {
+            secret,
+            expiration,
+            audience: None,
+            issuer: None,
+        }
+    }
+
+    /// Issue tokens for `audience` and reject tokens meant for any other audience
+    pub fn with_audience(mut self, audience: String) -> Self {
+        self.audience = Some(audience);
+        self
+    }
+
+    /// Issue tokens from `issuer` and reject tokens from any other issuer
+    pub fn with_issuer(mut self, issuer: String) -> Self {
+        self.issuer = Some(issuer);
+        self
     }
 
     /// Get token expiration time in seconds
@@ -27,14 +58,28 @@ impl JwtHandler {
         self.expiration
     }
 
-    /// Generate new JWT token
-    pub fn generate_token(&self) -> String {
+    /// Generate new JWT token for the default subject
+    pub fn generate_token(&self) -> Result<String, DomainError> {
+        self.generate_token_for(DEFAULT_SUBJECT)
+    }
+
+    /// Generate new JWT token attributed to `subject`
+    pub fn generate_token_for(&self, subject: &str) -> Result<String, DomainError> {
+        // An empty key would sign tokens that anyone can forge
+        if self.secret.is_empty() {
+            return Err(DomainError::TokenGeneration(
+                "signing secret is empty".to_string(),
+            ));
+        }
+
         let now = Utc::now().timestamp();
         let claims = Claims {
-            iss: "export-service".to_string(),
-            sub: "web-client".to_string(),
+            iss: self.issuer.as_deref().unwrap_or(DEFAULT_ISSUER).to_string(),
+            sub: subject.to_string(),
             exp: now + self.expiration,
             iat: now,
+            jti: uuid::Uuid::new_v4().to_string(),
+            aud: self.audience.clone(),
         };
 
         encode(
@@ -42,17 +87,92 @@ impl JwtHandler {
             &claims,
             &EncodingKey::from_secret(self.secret.as_bytes()),
         )
-        .unwrap_or_default()
+        .map_err(|e| DomainError::TokenGeneration(e.to_string()))
     }
 
     /// Validate JWT token
     pub fn validate_token(&self, token: &str) -> Result<Claims, String> {
+        let mut validation = Validation::default();
+        if let Some(audience) = &self.audience {
+            validation.set_audience(&[audience]);
+        }
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+        }
         decode::<Claims>(
             token,
             &DecodingKey::from_secret(self.secret.as_bytes()),
-            &Validation::default(),
+            &validation,
         )
         .map(|data| data.claims)
         .map_err(|e| e.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_token_validates() {
+        let handler = JwtHandler::new("secret".to_string(), 60);
+
+        let token = handler.generate_token().unwrap();
+        assert!(!token.is_empty());
+        assert_eq!(handler.validate_token(&token).unwrap().sub, "web-client");
+    }
+
+    #[test]
+    fn test_matching_audience_validates() {
+        let handler =
+            JwtHandler::new("secret".to_string(), 60).with_audience("reports".to_string());
+
+        let token = handler.generate_token().unwrap();
+        assert_eq!(handler.validate_token(&token).unwrap().aud.as_deref(), Some("reports"));
+    }
+
+    #[test]
+    fn test_mismatched_audience_is_rejected() {
+        let issuer =
+            JwtHandler::new("secret".to_string(), 60).with_audience("billing".to_string());
+        let verifier =
+            JwtHandler::new("secret".to_string(), 60).with_audience("reports".to_string());
+
+        let token = issuer.generate_token().unwrap();
+        let err = verifier.validate_token(&token).unwrap_err();
+        assert!(err.contains("InvalidAudience"), "{}", err);
+    }
+
+    #[test]
+    fn test_token_carries_issuer_and_subject() {
+        let handler =
+            JwtHandler::new("secret".to_string(), 60).with_issuer("reports-api".to_string());
+
+        let token = handler.generate_token_for("alice@example.com").unwrap();
+        let claims = handler.validate_token(&token).unwrap();
+        assert_eq!(claims.iss, "reports-api");
+        assert_eq!(claims.sub, "alice@example.com");
+    }
+
+    #[test]
+    fn test_mismatched_issuer_is_rejected_only_when_enforced() {
+        let issuer = JwtHandler::new("secret".to_string(), 60);
+        let verifier =
+            JwtHandler::new("secret".to_string(), 60).with_issuer("reports-api".to_string());
+
+        let token = issuer.generate_token().unwrap();
+        assert_eq!(issuer.validate_token(&token).unwrap().iss, DEFAULT_ISSUER);
+        let err = verifier.validate_token(&token).unwrap_err();
+        assert!(err.contains("InvalidIssuer"), "{}", err);
+    }
+
+    #[test]
+    fn test_empty_secret_is_an_error() {
+        let handler = JwtHandler::new(String::new(), 60);
+
+        assert!(matches!(
+            handler.generate_token(),
+            Err(DomainError::TokenGeneration(_))
+        ));
+    }
+}
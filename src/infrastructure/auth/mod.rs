@@ -1,3 +1,5 @@
+mod authenticator;
 mod jwt_handler;
 
-pub use jwt_handler::{JwtHandler, Claims};
+pub use authenticator::{ApiKeyAuthenticator, AuthScheme, API_KEY_HEADER};
+pub use jwt_handler::{JwtHandler, Claims, DEFAULT_SUBJECT};
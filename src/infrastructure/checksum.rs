@@ -0,0 +1,35 @@
+use sha2::{Digest, Sha256};
+
+/// Hex-encoded SHA-256 digest of the given bytes
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Check bytes against a previously computed hex SHA-256 digest
+pub fn verify_sha256(bytes: &[u8], expected_hex: &str) -> bool {
+    sha256_hex(bytes).eq_ignore_ascii_case(expected_hex.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256_hex_known_value() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_verify_detects_corruption() {
+        let bytes = b"Name\nWidget\n".to_vec();
+        let checksum = sha256_hex(&bytes);
+        assert!(verify_sha256(&bytes, &checksum));
+
+        let mut corrupted = bytes.clone();
+        corrupted[0] = b'X';
+        assert!(!verify_sha256(&corrupted, &checksum));
+    }
+}
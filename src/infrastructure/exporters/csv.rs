@@ -1,25 +1,547 @@
-use csv::Writer;
-use crate::application::ports::ExportService;
-use crate::domain::models::ExportData;
+use csv::WriterBuilder;
+use std::borrow::Cow;
+use std::io::Write;
+use crate::application::ports::{ExportService, StreamingExportService};
+use crate::domain::grouping::{grouped_rows, GroupedRow};
+use crate::domain::dates::DateFormatter;
+use crate::domain::locale::Locale;
+use crate::domain::models::{ColumnType, CsvEncoding, ExportData, ExportFormat};
 
 pub struct CsvExporter;
 
+/// UTF-8 byte order mark, written when `csv_bom` is set
+const UTF8_BOM: [u8; 3] = [0xEF, 0xBB, 0xBF];
+
+/// UTF-16LE byte order mark
+const UTF16LE_BOM: [u8; 2] = [0xFF, 0xFE];
+
+/// Sink adapter that re-encodes UTF-8 output as UTF-16LE
+///
+/// The decoder keeps any multi-byte character split across writes until the rest
+/// of it arrives, so the CSV writer can flush at arbitrary byte boundaries.
+struct Utf16LeWriter<'a> {
+    inner: &'a mut dyn Write,
+    decoder: encoding_rs::Decoder,
+}
+
+impl<'a> Utf16LeWriter<'a> {
+    /// Wrap the sink and write the byte order mark
+    fn new(inner: &'a mut dyn Write) -> std::io::Result<Self> {
+        inner.write_all(&UTF16LE_BOM)?;
+        Ok(Self {
+            inner,
+            decoder: encoding_rs::UTF_8.new_decoder_without_bom_handling(),
+        })
+    }
+
+    fn encode(&mut self, buf: &[u8], last: bool) -> std::io::Result<()> {
+        let capacity = self
+            .decoder
+            .max_utf16_buffer_length(buf.len())
+            .ok_or_else(|| std::io::Error::other("CSV chunk too large to re-encode"))?;
+        let mut units = vec![0u16; capacity];
+        let (_, _, written, _) = self.decoder.decode_to_utf16(buf, &mut units, last);
+        let bytes: Vec<u8> = units[..written]
+            .iter()
+            .flat_map(|unit| unit.to_le_bytes())
+            .collect();
+        self.inner.write_all(&bytes)
+    }
+
+    /// Flush any incomplete trailing character and the underlying sink
+    fn finish(mut self) -> std::io::Result<()> {
+        self.encode(&[], true)?;
+        self.inner.flush()
+    }
+}
+
+impl Write for Utf16LeWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.encode(buf, false)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Per-export field formatting settings derived from the export options
+struct FieldFormat<'a> {
+    newline_replacement: Option<&'a str>,
+    locale: Option<Locale>,
+    dates: Option<DateFormatter>,
+}
+
+impl<'a> FieldFormat<'a> {
+    fn from_data(data: &'a ExportData) -> Self {
+        let options = data.options.as_ref();
+        Self {
+            newline_replacement: options.and_then(|o| o.newline_replacement.as_deref()),
+            locale: options
+                .and_then(|o| o.locale.as_deref())
+                .and_then(Locale::from_tag),
+            dates: DateFormatter::from_options(options),
+        }
+    }
+
+    /// Format a single field; locale and date formatting only apply to data rows
+    fn apply<'c>(
+        &self,
+        data: &ExportData,
+        cell: &'c str,
+        col_idx: usize,
+        is_data_row: bool,
+    ) -> Cow<'c, str> {
+        let mut value = Cow::Borrowed(cell);
+        if is_data_row {
+            let column_type = data.column_type(col_idx);
+            match (&self.dates, &self.locale) {
+                (Some(dates), _) if column_type == ColumnType::Date => {
+                    value = Cow::Owned(dates.format_cell(cell))
+                }
+                (_, Some(locale)) => value = Cow::Owned(locale.format_cell(cell, column_type)),
+                _ => {}
+            }
+        }
+        if let Some(replacement) = self.newline_replacement {
+            value = Cow::Owned(CsvExporter::replace_newlines(&value, replacement));
+        }
+        value
+    }
+}
+
+impl CsvExporter {
+    /// Prefix the first summary field with the configured marker, if any
+    ///
+    /// A non-empty first field is kept apart from the marker as `TOTAL: Total`.
+    fn mark_summary(data: &ExportData, summary: &[String]) -> Vec<String> {
+        let mut record = summary.to_vec();
+        let marker = data.options.as_ref().and_then(|o| o.summary_marker.as_deref());
+        if let (Some(marker), Some(first)) = (marker, record.first_mut()) {
+            *first = if first.is_empty() {
+                marker.to_string()
+            } else {
+                format!("{}: {}", marker, first)
+            };
+        }
+        record
+    }
+
+    /// A record with the group name first and the remaining fields empty
+    fn group_record(data: &ExportData, name: &str) -> Vec<String> {
+        let mut record = vec![String::new(); data.headers.len()];
+        record[0] = name.to_string();
+        record
+    }
+
+    /// Single-byte field delimiter from the options, defaulting to a comma
+    ///
+    /// TSV always uses a tab; fields holding one are quoted like any other special
+    /// character, since the writer quotes only when necessary.
+    fn delimiter(data: &ExportData) -> Result<u8, String> {
+        if data.format == ExportFormat::Tsv {
+            return Ok(b'\t');
+        }
+        match data.options.as_ref().and_then(|o| o.delimiter.as_deref()) {
+            None => Ok(b','),
+            Some(delimiter) if delimiter.len() == 1 => Ok(delimiter.as_bytes()[0]),
+            Some(delimiter) => Err(format!(
+                "CSV delimiter must be a single ASCII character, got {:?}",
+                delimiter
+            )),
+        }
+    }
+
+    /// Replace `\r\n`, `\n` and `\r` inside a cell with the given replacement
+    fn replace_newlines(cell: &str, replacement: &str) -> String {
+        cell.replace("\r\n", "\n").replace(['\n', '\r'], replacement)
+    }
+}
+
 impl ExportService for CsvExporter {
     fn export(&self, data: &ExportData) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
         let mut buffer = Vec::new();
-        {
-            let mut writer = Writer::from_writer(&mut buffer);
+        self.export_to(data, &mut buffer)?;
+        Ok(buffer)
+    }
+}
 
-            // Write headers
-            writer.write_record(&data.headers)?;
+impl StreamingExportService for CsvExporter {
+    fn export_to(
+        &self,
+        data: &ExportData,
+        sink: &mut dyn Write,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let options = data.options.as_ref();
+        let encoding = options.and_then(|o| o.csv_encoding).unwrap_or_default();
 
-            // Write rows
-            for row in &data.rows {
-                writer.write_record(row)?;
+        match encoding {
+            CsvEncoding::Utf8 => {
+                if options.and_then(|o| o.csv_bom).unwrap_or(false) {
+                    sink.write_all(&UTF8_BOM)?;
+                }
+                Self::write_records(data, sink)
             }
+            CsvEncoding::Utf16le => {
+                let mut utf16 = Utf16LeWriter::new(sink)?;
+                Self::write_records(data, &mut utf16)?;
+                Ok(utf16.finish()?)
+            }
+        }
+    }
+}
+
+impl CsvExporter {
+    /// Write the header, data and summary records as UTF-8
+    fn write_records(
+        data: &ExportData,
+        sink: &mut dyn Write,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let field_format = FieldFormat::from_data(data);
+
+        let mut writer = WriterBuilder::new()
+            .delimiter(Self::delimiter(data)?)
+            .from_writer(sink);
+
+        let mut write_record = |record: &[String], is_data_row: bool| -> csv::Result<()> {
+            let fields: Vec<Cow<str>> = record
+                .iter()
+                .enumerate()
+                .map(|(col_idx, cell)| field_format.apply(data, cell, col_idx, is_data_row))
+                .collect();
+            writer.write_record(fields.iter().map(|field| field.as_bytes()))
+        };
 
-            writer.flush()?;
+        // Write headers unless include_header_row is false
+        let include_header = data
+            .options
+            .as_ref()
+            .and_then(|o| o.include_header_row)
+            .unwrap_or(true);
+        if include_header {
+            write_record(&data.headers, false)?;
         }
-        Ok(buffer)
+
+        // Write rows; a group header record carries the group name in its first field
+        for entry in grouped_rows(data) {
+            match entry {
+                GroupedRow::GroupHeader(name) => {
+                    write_record(&CsvExporter::group_record(data, name), false)?
+                }
+                GroupedRow::Data(row) => write_record(row, true)?,
+            }
+        }
+
+        // Write the summary row as the trailing record
+        if let Some(summary) = &data.summary_row {
+            write_record(&CsvExporter::mark_summary(data, summary), true)?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{ColumnMetadata, ColumnRef, ExportOptions};
+
+    fn sample_data(options: Option<ExportOptions>) -> ExportData {
+        ExportData {
+            title: "Notes".to_string(),
+            format: ExportFormat::Csv,
+            headers: vec!["Id".to_string(), "Note".to_string()],
+            rows: vec![vec!["1".to_string(), "first line\nsecond line\r\nthird".to_string()]],
+            options,
+            column_metadata: None,
+            summary_row: None,
+            sheets: None,
+        }
+    }
+
+    fn export_string(data: &ExportData) -> String {
+        String::from_utf8(CsvExporter.export(data).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_newlines_are_quoted_by_default() {
+        let output = export_string(&sample_data(None));
+
+        assert!(output.contains("\"first line\nsecond line\r\nthird\""));
+    }
+
+    #[test]
+    fn test_newline_replacement_removes_embedded_newlines() {
+        let options = ExportOptions {
+            newline_replacement: Some(" ".to_string()),
+            ..Default::default()
+        };
+        let output = export_string(&sample_data(Some(options)));
+
+        assert_eq!(output, "Id,Note\n1,first line second line third\n");
+    }
+
+    #[test]
+    fn test_locale_controls_decimal_separator() {
+        let localized = |tag: &str| ExportData {
+            headers: vec!["Item".to_string(), "Amount".to_string()],
+            rows: vec![vec!["Widget".to_string(), "1234.5".to_string()]],
+            options: Some(ExportOptions {
+                locale: Some(tag.to_string()),
+                ..Default::default()
+            }),
+            column_metadata: Some(vec![ColumnMetadata::text(), ColumnMetadata::number()]),
+            ..sample_data(None)
+        };
+
+        assert_eq!(export_string(&localized("de-DE")), "Item,Amount\nWidget,\"1.234,5\"\n");
+        assert_eq!(export_string(&localized("en-US")), "Item,Amount\nWidget,\"1,234.5\"\n");
+    }
+
+    #[test]
+    fn test_tsv_uses_tabs_and_quotes_only_fields_with_tabs() {
+        let data = ExportData {
+            format: ExportFormat::Tsv,
+            rows: vec![
+                vec!["1".to_string(), "a\tb".to_string()],
+                vec!["2".to_string(), "plain, text".to_string()],
+            ],
+            // The delimiter option does not apply to TSV
+            options: Some(ExportOptions {
+                delimiter: Some(";".to_string()),
+                ..Default::default()
+            }),
+            ..sample_data(None)
+        };
+
+        let bytes = CsvExporter.export(&data).unwrap();
+
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            "Id\tNote\n1\t\"a\tb\"\n2\tplain, text\n"
+        );
+        assert_eq!(ExportFormat::from_name("TSV"), Some(ExportFormat::Tsv));
+        assert_eq!(ExportFormat::Tsv.extension(), "tsv");
+        assert_eq!(ExportFormat::Tsv.mime_type(), "text/tab-separated-values");
+    }
+
+    #[test]
+    fn test_summary_row_is_final_record() {
+        let data = ExportData {
+            rows: vec![
+                vec!["1".to_string(), "first".to_string()],
+                vec!["2".to_string(), "second".to_string()],
+            ],
+            summary_row: Some(vec!["".to_string(), "2 notes".to_string()]),
+            options: Some(ExportOptions {
+                summary_marker: Some("TOTAL".to_string()),
+                ..Default::default()
+            }),
+            ..sample_data(None)
+        };
+
+        let bytes = CsvExporter.export(&data).unwrap();
+        let records: Vec<csv::StringRecord> = csv::Reader::from_reader(bytes.as_slice())
+            .records()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let last = records.last().unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(last.len(), data.headers.len());
+        assert_eq!(last, vec!["TOTAL", "2 notes"]);
+    }
+
+    #[test]
+    fn test_summary_marker_is_separated_from_first_field() {
+        let data = ExportData {
+            summary_row: Some(vec!["Total".to_string(), "3".to_string()]),
+            options: Some(ExportOptions {
+                summary_marker: Some("TOTAL".to_string()),
+                ..Default::default()
+            }),
+            ..sample_data(None)
+        };
+
+        let bytes = CsvExporter.export(&data).unwrap();
+        let records: Vec<csv::StringRecord> = csv::Reader::from_reader(bytes.as_slice())
+            .records()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(records.last().unwrap(), vec!["TOTAL: Total", "3"]);
+    }
+
+    #[test]
+    fn test_group_header_record_per_group() {
+        let data = ExportData {
+            headers: vec!["Team".to_string(), "Name".to_string()],
+            rows: vec![
+                vec!["Red".to_string(), "Ann".to_string()],
+                vec!["Red".to_string(), "Bob".to_string()],
+                vec!["Blue".to_string(), "Cy".to_string()],
+            ],
+            options: Some(ExportOptions {
+                group_by: Some(ColumnRef::Name("Team".to_string())),
+                ..Default::default()
+            }),
+            ..sample_data(None)
+        };
+
+        assert_eq!(
+            export_string(&data),
+            "Team,Name\nRed,\nRed,Ann\nRed,Bob\nBlue,\nBlue,Cy\n"
+        );
+    }
+
+    #[test]
+    fn test_header_row_can_be_omitted() {
+        let with_header = |include: Option<bool>| ExportData {
+            rows: vec![
+                vec!["1".to_string(), "first".to_string()],
+                vec!["2".to_string(), "second".to_string()],
+            ],
+            options: Some(ExportOptions {
+                include_header_row: include,
+                ..Default::default()
+            }),
+            ..sample_data(None)
+        };
+
+        let output = export_string(&with_header(Some(false)));
+        assert_eq!(output.lines().next(), Some("1,first"));
+        assert_eq!(output, "1,first\n2,second\n");
+        assert!(export_string(&with_header(None)).starts_with("Id,Note\n"));
+        assert!(export_string(&with_header(Some(true))).starts_with("Id,Note\n"));
+    }
+
+    #[test]
+    fn test_delimiter_option_separates_fields() {
+        let with_delimiter = |delimiter: &str| ExportData {
+            rows: vec![vec!["1".to_string(), "a;b".to_string()]],
+            options: Some(ExportOptions {
+                delimiter: Some(delimiter.to_string()),
+                ..Default::default()
+            }),
+            ..sample_data(None)
+        };
+
+        assert_eq!(export_string(&with_delimiter(";")), "Id;Note\n1;\"a;b\"\n");
+        assert_eq!(export_string(&with_delimiter("\t")), "Id\tNote\n1\ta;b\n");
+    }
+
+    #[test]
+    fn test_multi_character_delimiter_is_an_error() {
+        for delimiter in ["||", "", "\u{a7}"] {
+            let data = sample_data(Some(ExportOptions {
+                delimiter: Some(delimiter.to_string()),
+                ..Default::default()
+            }));
+
+            let err = CsvExporter.export(&data).unwrap_err();
+            assert!(err.to_string().contains("delimiter"), "{}", err);
+        }
+    }
+
+    fn decode_utf16le(bytes: &[u8]) -> String {
+        let units: Vec<u16> = bytes
+            .chunks(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16(&units).unwrap()
+    }
+
+    #[test]
+    fn test_utf16le_output_has_bom_and_round_trips() {
+        let utf8 = ExportData {
+            rows: vec![
+                vec![
+                    "1".to_string(),
+                    "\u{0e2a}\u{0e27}\u{0e31}\u{0e2a}\u{0e14}\u{0e35}".to_string(),
+                ],
+                vec!["2".to_string(), "caf\u{e9}, \"quoted\"".to_string()],
+            ],
+            ..sample_data(None)
+        };
+        let utf16 = ExportData {
+            options: Some(ExportOptions {
+                csv_encoding: Some(CsvEncoding::Utf16le),
+                ..Default::default()
+            }),
+            ..utf8.clone()
+        };
+
+        let bytes = CsvExporter.export(&utf16).unwrap();
+        assert_eq!(&bytes[..2], &[0xFF, 0xFE]);
+
+        let decoded = decode_utf16le(&bytes[2..]);
+        assert_eq!(decoded, export_string(&utf8));
+        let records: Vec<csv::StringRecord> = csv::Reader::from_reader(decoded.as_bytes())
+            .records()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0], vec!["1", utf8.rows[0][1].as_str()]);
+    }
+
+    #[test]
+    fn test_utf8_bom_is_written_once_when_requested() {
+        let with_bom = |csv_bom| {
+            sample_data(Some(ExportOptions {
+                csv_bom,
+                ..Default::default()
+            }))
+        };
+
+        let bytes = CsvExporter.export(&with_bom(Some(true))).unwrap();
+        assert_eq!(&bytes[..3], &UTF8_BOM);
+        assert!(!bytes[3..].starts_with(&UTF8_BOM));
+        assert_eq!(&bytes[3..], export_string(&sample_data(None)).as_bytes());
+
+        for csv_bom in [None, Some(false)] {
+            let bytes = CsvExporter.export(&with_bom(csv_bom)).unwrap();
+            assert!(!bytes.starts_with(&UTF8_BOM), "{:?}", csv_bom);
+        }
+    }
+
+    #[test]
+    fn test_streamed_bytes_match_buffered_export() {
+        let rows: Vec<Vec<String>> = (0..500)
+            .map(|i| vec![i.to_string(), format!("note \u{0e01}{}, \"quoted\"", i)])
+            .collect();
+        for encoding in [CsvEncoding::Utf8, CsvEncoding::Utf16le] {
+            let data = ExportData {
+                rows: rows.clone(),
+                summary_row: Some(vec!["Total".to_string(), "500".to_string()]),
+                ..sample_data(Some(ExportOptions {
+                    csv_encoding: Some(encoding),
+                    ..Default::default()
+                }))
+            };
+
+            // A small buffer forces many partial writes, as when streaming a response
+            let mut streamed = Vec::new();
+            let mut writer = std::io::BufWriter::with_capacity(7, &mut streamed);
+            CsvExporter.export_to(&data, &mut writer).unwrap();
+            writer.flush().unwrap();
+            drop(writer);
+
+            assert_eq!(streamed, CsvExporter.export(&data).unwrap(), "{:?}", encoding);
+        }
+    }
+
+    #[test]
+    fn test_utf16le_writer_joins_split_characters() {
+        let text = "\u{0e01}\u{0e02}\u{20ac}";
+        let mut out = Vec::new();
+        let mut writer = Utf16LeWriter::new(&mut out).unwrap();
+        for byte in text.as_bytes() {
+            writer.write_all(std::slice::from_ref(byte)).unwrap();
+        }
+        writer.finish().unwrap();
+
+        assert_eq!(decode_utf16le(&out[2..]), text);
     }
 }
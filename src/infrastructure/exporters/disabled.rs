@@ -0,0 +1,24 @@
+use crate::application::ports::ExportService;
+use crate::domain::models::{ExportData, ExportFormat};
+
+/// Stand-in for an exporter whose Cargo feature is disabled
+pub struct DisabledExporter {
+    format: ExportFormat,
+}
+
+impl DisabledExporter {
+    pub fn new(format: ExportFormat) -> Self {
+        Self { format }
+    }
+}
+
+impl ExportService for DisabledExporter {
+    fn export(&self, _data: &ExportData) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        Err(format!(
+            "{} export is not enabled in this build (enable the `{}` feature)",
+            self.format.name(),
+            self.format.name()
+        )
+        .into())
+    }
+}
@@ -1,24 +1,269 @@
 use rust_xlsxwriter::*;
+use chrono::Datelike;
 use crate::application::ports::ExportService;
-use crate::domain::models::ExportData;
+use crate::config::ExcelNumberFormats;
+use crate::domain::dates::DateFormatter;
+use crate::domain::grouping::{grouped_table_rows, GroupedRow};
+use crate::domain::models::{Alignment, ColumnType, ExportData, PercentScale, TableView};
 
-pub struct ExcelExporter;
+pub struct ExcelExporter {
+    number_formats: ExcelNumberFormats,
+}
 
-impl ExportService for ExcelExporter {
-    fn export(&self, data: &ExportData) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
-        let mut workbook = Workbook::new();
-        let worksheet = workbook.add_worksheet();
+impl ExcelExporter {
+    pub fn new() -> Self {
+        Self {
+            number_formats: ExcelNumberFormats::default(),
+        }
+    }
+
+    /// Use custom number format codes for numeric columns
+    pub fn with_number_formats(mut self, number_formats: ExcelNumberFormats) -> Self {
+        self.number_formats = number_formats;
+        self
+    }
+
+    /// Number format code for a column type, or None for columns written as strings
+    fn number_format(&self, column_type: ColumnType) -> Option<&str> {
+        match column_type {
+            ColumnType::Number => Some(&self.number_formats.number),
+            ColumnType::Currency => Some(&self.number_formats.currency),
+            ColumnType::Percentage => Some(&self.number_formats.percentage),
+            ColumnType::Date => Some(&self.number_formats.date),
+            ColumnType::Text | ColumnType::QrCode => None,
+        }
+    }
+}
+
+/// Whether a format code prints `%` only as a literal (quoted or escaped), without
+/// the bare `%` that makes Excel multiply by 100
+fn shows_literal_percent(code: &str) -> bool {
+    let (mut literal, mut quoted) = (false, false);
+    let mut chars = code.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => quoted = !quoted,
+            '\\' if !quoted => literal |= chars.next() == Some('%'),
+            '%' if quoted => literal = true,
+            '%' => return false,
+            _ => {}
+        }
+    }
+    literal
+}
+
+/// Column width used unless `auto_fit_columns` is set
+const DEFAULT_COLUMN_WIDTH: usize = 20;
+/// Bounds for auto-fitted column widths, in characters
+const MIN_COLUMN_WIDTH: usize = 8;
+const MAX_COLUMN_WIDTH: usize = 80;
+
+/// Width of each column in characters: fixed, or fitted to the longest header/cell
+fn column_widths(data: TableView<'_>) -> Vec<usize> {
+    let auto_fit = data
+        .options
+        .and_then(|o| o.auto_fit_columns)
+        .unwrap_or(false);
+    if !auto_fit {
+        return vec![DEFAULT_COLUMN_WIDTH; data.headers.len()];
+    }
 
-        // Write headers (row 0)
-        for (col, header) in data.headers.iter().enumerate() {
-            worksheet.write_string(0, col as u16, header)?;
-            worksheet.set_column_width(col as u16, 20)?;
+    let mut widths: Vec<usize> = data.headers.iter().map(|h| h.chars().count()).collect();
+    for row in data.rows.iter().map(Vec::as_slice).chain(data.summary_row) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    widths
+        .into_iter()
+        .map(|width| width.clamp(MIN_COLUMN_WIDTH, MAX_COLUMN_WIDTH))
+        .collect()
+}
+
+/// Parse a `#RRGGBB` or `RRGGBB` hex color
+fn parse_hex_color(value: &str) -> Result<Color, String> {
+    let hex = value.strip_prefix('#').unwrap_or(value);
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!(
+            "Invalid header_background color {:?}: expected #RRGGBB",
+            value
+        ));
+    }
+    u32::from_str_radix(hex, 16)
+        .map(Color::RGB)
+        .map_err(|e| e.to_string())
+}
+
+/// Numeric value written for a cell; percentages become fractions of one, or whole
+/// percents when `whole_percents` is set
+///
+/// A trailing `%` always marks a whole percent; otherwise `scale` says how the
+/// percentage column's values are expressed.
+fn cell_number(
+    cell: &str,
+    column_type: ColumnType,
+    scale: PercentScale,
+    whole_percents: bool,
+) -> Option<f64> {
+    let cell = cell.trim();
+    let parse = |text: &str| text.parse::<f64>().ok().filter(|n| n.is_finite());
+    let (text, sent_whole) = match (column_type, cell.strip_suffix('%')) {
+        (ColumnType::Percentage, Some(whole)) => (whole.trim_end(), true),
+        (ColumnType::Percentage, None) => (cell, scale == PercentScale::Whole),
+        _ => return parse(cell),
+    };
+    let number = parse(text)?;
+    Some(match (sent_whole, whole_percents) {
+        (true, false) => number / 100.0,
+        (false, true) => number * 100.0,
+        _ => number,
+    })
+}
+
+/// Excel horizontal alignment for a column alignment
+fn excel_align(align: Alignment) -> FormatAlign {
+    match align {
+        Alignment::Left => FormatAlign::Left,
+        Alignment::Center => FormatAlign::Center,
+        Alignment::Right => FormatAlign::Right,
+    }
+}
+
+/// Parse a date cell (ISO-8601 unless `date_input_format` says otherwise) into an Excel date
+fn excel_date(cell: &str, dates: &DateFormatter) -> Option<ExcelDateTime> {
+    let date = dates.parse(cell)?;
+    let year = u16::try_from(date.year()).ok()?;
+    ExcelDateTime::from_ymd(year, date.month() as u8, date.day() as u8).ok()
+}
+
+impl Default for ExcelExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExcelExporter {
+    /// Write one table (headers, data rows and summary row) into a worksheet
+    fn write_table(
+        &self,
+        worksheet: &mut Worksheet,
+        data: TableView<'_>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let column_types: Vec<ColumnType> =
+            (0..data.headers.len()).map(|col_idx| data.column_type(col_idx)).collect();
+        // Explicit column alignments; other cells keep Excel's own by-value alignment
+        let align_formats: Vec<Option<Format>> = (0..data.headers.len())
+            .map(|col_idx| {
+                data.column_alignment(col_idx)
+                    .map(|align| Format::new().set_align(excel_align(align)))
+            })
+            .collect();
+        let aligned = |col_idx: usize| -> Format {
+            align_formats.get(col_idx).cloned().flatten().unwrap_or_default()
+        };
+        // Text number format keeps values like "00123" as text when edited in Excel
+        let text_formats: Vec<Format> = (0..data.headers.len())
+            .map(|col_idx| aligned(col_idx).set_num_format("@"))
+            .collect();
+        let column_formats: Vec<Option<Format>> = (0..data.headers.len())
+            .map(|col_idx| {
+                self.number_format(data.column_type(col_idx))
+                    .map(|code| aligned(col_idx).set_num_format(code))
+            })
+            .collect();
+
+        // Write headers (row 0), styled by header_bold and header_background
+        let options = data.options;
+        let header_bold = options.and_then(|o| o.header_bold).unwrap_or(false);
+        let header_background = options
+            .and_then(|o| o.header_background.as_deref())
+            .map(parse_hex_color)
+            .transpose()?;
+        let header_format = (header_bold || header_background.is_some()).then(|| {
+            let format = Format::new();
+            let format = if header_bold { format.set_bold() } else { format };
+            match header_background {
+                Some(color) => format.set_background_color(color),
+                None => format,
+            }
+        });
+        let widths = column_widths(data);
+        for (col, (header, width)) in data.headers.iter().zip(widths).enumerate() {
+            match &header_format {
+                Some(format) => worksheet.write_string_with_format(0, col as u16, header, format)?,
+                None => worksheet.write_string(0, col as u16, header)?,
+            };
+            worksheet.set_column_width(col as u16, width as f64)?;
         }
 
-        // Write data rows
-        for (row_idx, row) in data.rows.iter().enumerate() {
+        let percent_scale = options.and_then(|o| o.percent_scale).unwrap_or_default();
+        // A percentage format showing only a literal `%` (e.g. `0.00"%"`) does not scale
+        // by 100, so its cells stay whole percents as they were before fractions
+        let whole_percents = shows_literal_percent(&self.number_formats.percentage);
+        let dates = DateFormatter::from_options(options).unwrap_or_default();
+
+        // Write data rows; group headers are bold and merged across the table
+        let group_format = Format::new().set_bold();
+        let last_col = data.headers.len().saturating_sub(1) as u16;
+        let entries = grouped_table_rows(data);
+        let summary_row_num = entries.len() as u32 + 1;
+        for (row_idx, entry) in entries.into_iter().enumerate() {
+            let row = match entry {
+                GroupedRow::GroupHeader(name) => {
+                    let row_num = (row_idx + 1) as u32;
+                    if last_col > 0 {
+                        worksheet.merge_range(row_num, 0, row_num, last_col, name, &group_format)?;
+                    } else {
+                        worksheet.write_string_with_format(row_num, 0, name, &group_format)?;
+                    }
+                    continue;
+                }
+                GroupedRow::Data(row) => row,
+            };
             for (col_idx, cell) in row.iter().enumerate() {
-                worksheet.write_string((row_idx + 1) as u32, col_idx as u16, cell)?;
+                let (row_num, col_num) = ((row_idx + 1) as u32, col_idx as u16);
+                let number_format = column_formats.get(col_idx).and_then(Option::as_ref);
+                let column_type = column_types.get(col_idx).copied().unwrap_or_default();
+                let is_date = column_type == ColumnType::Date;
+                let date = if is_date { excel_date(cell, &dates) } else { None };
+                let number = cell_number(cell, column_type, percent_scale, whole_percents)
+                    .filter(|_| !is_date);
+                let align_format = align_formats.get(col_idx).and_then(Option::as_ref);
+                if data.is_forced_text(col_idx) {
+                    let text_format = &text_formats[col_idx];
+                    worksheet.write_string_with_format(row_num, col_num, cell, text_format)?;
+                } else if let (Some(format), Some(date)) = (number_format, date) {
+                    worksheet.write_datetime_with_format(row_num, col_num, date, format)?;
+                } else if let (Some(format), Some(number)) = (number_format, number) {
+                    worksheet.write_number_with_format(row_num, col_num, number, format)?;
+                } else if let Some(format) = align_format {
+                    worksheet.write_string_with_format(row_num, col_num, cell, format)?;
+                } else {
+                    worksheet.write_string(row_num, col_num, cell)?;
+                }
+            }
+        }
+
+        // Write the summary row in bold below the data, numbers keeping their column format
+        if let Some(summary) = data.summary_row {
+            for (col_idx, cell) in summary.iter().enumerate() {
+                let col_num = col_idx as u16;
+                let format = column_formats
+                    .get(col_idx)
+                    .cloned()
+                    .flatten()
+                    .unwrap_or_else(|| aligned(col_idx))
+                    .set_bold();
+                let column_type = column_types.get(col_idx).copied().unwrap_or_default();
+                let is_numeric = column_type.is_right_aligned();
+                let number = cell_number(cell, column_type, percent_scale, whole_percents);
+                let row_num = summary_row_num;
+                match number.filter(|_| is_numeric) {
+                    Some(number) => {
+                        worksheet.write_number_with_format(row_num, col_num, number, &format)?
+                    }
+                    None => worksheet.write_string_with_format(row_num, col_num, cell, &format)?,
+                };
             }
         }
 
@@ -27,6 +272,29 @@ impl ExportService for ExcelExporter {
             if opts.freeze_headers.unwrap_or(false) {
                 worksheet.set_freeze_panes(1, 0)?;
             }
+            // The filter spans the header and data rows; the summary row stays outside it
+            if opts.autofilter.unwrap_or(false) {
+                worksheet.autofilter(0, 0, summary_row_num - 1, last_col)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ExportService for ExcelExporter {
+    fn export(&self, data: &ExportData) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let mut workbook = Workbook::new();
+        match &data.sheets {
+            // One named worksheet per sheet, in request order
+            Some(sheets) => {
+                for sheet in sheets {
+                    let worksheet = workbook.add_worksheet();
+                    worksheet.set_name(&sheet.title)?;
+                    self.write_table(worksheet, data.sheet_table(sheet))?;
+                }
+            }
+            None => self.write_table(workbook.add_worksheet(), data.table())?,
         }
 
         // Return as bytes
@@ -35,3 +303,478 @@ impl ExportService for ExcelExporter {
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{ColumnMetadata, ColumnRef, ExportFormat, ExportOptions, Sheet};
+    use std::io::{Cursor, Read};
+
+    fn sample_data() -> ExportData {
+        ExportData {
+            title: "Codes".to_string(),
+            format: ExportFormat::Excel,
+            headers: vec!["Code".to_string(), "Name".to_string()],
+            rows: vec![vec!["00123".to_string(), "Widget".to_string()]],
+            options: None,
+            column_metadata: None,
+            summary_row: None,
+            sheets: None,
+        }
+    }
+
+    /// Read an XML part (e.g. `xl/worksheets/sheet1.xml`) from the xlsx archive
+    fn read_part(bytes: &[u8], name: &str) -> String {
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let mut part = String::new();
+        archive
+            .by_name(name)
+            .unwrap()
+            .read_to_string(&mut part)
+            .unwrap();
+        part
+    }
+
+    #[test]
+    fn test_forced_text_column_keeps_leading_zeros() {
+        let data = ExportData {
+            column_metadata: Some(vec![
+                ColumnMetadata::number().with_force_text(),
+                ColumnMetadata::text(),
+            ]),
+            ..sample_data()
+        };
+
+        let bytes = ExcelExporter::new().export(&data).unwrap();
+        let sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+        let shared_strings = read_part(&bytes, "xl/sharedStrings.xml");
+        let styles = read_part(&bytes, "xl/styles.xml");
+
+        // A2 is a shared string cell holding the original "00123"
+        assert!(sheet.contains(r#"<c r="A2" s="1" t="s">"#), "{}", sheet);
+        assert!(shared_strings.contains("<t>00123</t>"));
+        // Text number format is attached to the cell
+        assert!(styles.contains(r#"formatCode="@""#), "{}", styles);
+    }
+
+    #[test]
+    fn test_explicit_alignment_is_applied_to_cells() {
+        let data = ExportData {
+            column_metadata: Some(vec![
+                ColumnMetadata::number().with_align(Alignment::Center),
+                ColumnMetadata::text(),
+            ]),
+            ..sample_data()
+        };
+
+        let bytes = ExcelExporter::new().export(&data).unwrap();
+        let sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+        let styles = read_part(&bytes, "xl/styles.xml");
+
+        // The number keeps its format and gains the centered style; B2 stays unstyled
+        assert!(sheet.contains(r#"<c r="A2" s="1"><v>123</v>"#), "{}", sheet);
+        assert!(sheet.contains(r#"<c r="B2" t="s">"#), "{}", sheet);
+        assert!(styles.contains(r#"<alignment horizontal="center"/>"#), "{}", styles);
+    }
+
+    #[test]
+    fn test_custom_currency_format_code_is_applied() {
+        let data = ExportData {
+            headers: vec!["Item".to_string(), "Price".to_string()],
+            rows: vec![vec!["Widget".to_string(), "1234.5".to_string()]],
+            column_metadata: Some(vec![ColumnMetadata::text(), ColumnMetadata::currency()]),
+            ..sample_data()
+        };
+        let formats = ExcelNumberFormats {
+            currency: "[$\u{20ac}-407] #,##0.00".to_string(),
+            ..Default::default()
+        };
+
+        let bytes = ExcelExporter::new()
+            .with_number_formats(formats)
+            .export(&data)
+            .unwrap();
+        let sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+        let styles = read_part(&bytes, "xl/styles.xml");
+
+        // B2 is a numeric cell styled with the custom currency code
+        assert!(sheet.contains(r#"<c r="B2" s="1"><v>1234.5</v></c>"#), "{}", sheet);
+        assert!(styles.contains("formatCode=\"[$\u{20ac}-407] #,##0.00\""), "{}", styles);
+    }
+
+    #[test]
+    fn test_number_column_cells_are_numeric_unless_unparseable() {
+        let data = ExportData {
+            headers: vec!["Item".to_string(), "Price".to_string()],
+            rows: vec![
+                vec!["42".to_string(), "99.99".to_string()],
+                vec!["Gadget".to_string(), "n/a".to_string()],
+                vec!["Empty".to_string(), "".to_string()],
+            ],
+            column_metadata: Some(vec![ColumnMetadata::text(), ColumnMetadata::number()]),
+            ..sample_data()
+        };
+
+        let bytes = ExcelExporter::new().export(&data).unwrap();
+        let sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+
+        assert!(sheet.contains(r#"<c r="B2" s="1"><v>99.99</v></c>"#), "{}", sheet);
+        // Unparseable values and text columns stay strings
+        assert!(sheet.contains(r#"<c r="B3" t="s">"#), "{}", sheet);
+        assert!(sheet.contains(r#"<c r="A2" t="s">"#), "{}", sheet);
+    }
+
+    fn percent_data(percent_scale: Option<PercentScale>) -> ExportData {
+        ExportData {
+            headers: vec!["Region".to_string(), "Growth".to_string()],
+            rows: vec![
+                vec!["North".to_string(), "12.5".to_string()],
+                vec!["South".to_string(), "7%".to_string()],
+                vec!["East".to_string(), "flat".to_string()],
+            ],
+            options: Some(ExportOptions {
+                percent_scale,
+                ..Default::default()
+            }),
+            column_metadata: Some(vec![ColumnMetadata::text(), ColumnMetadata::percentage()]),
+            ..sample_data()
+        }
+    }
+
+    #[test]
+    fn test_percentage_cells_are_fractions_with_percent_format() {
+        let bytes = ExcelExporter::new().export(&percent_data(None)).unwrap();
+        let sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+        let styles = read_part(&bytes, "xl/styles.xml");
+
+        assert!(sheet.contains(r#"<c r="B2" s="1"><v>0.125</v></c>"#), "{}", sheet);
+        assert!(sheet.contains(r#"<c r="B3" s="1"><v>0.07</v></c>"#), "{}", sheet);
+        // Unparseable values fall back to text
+        assert!(sheet.contains(r#"<c r="B4" t="s">"#), "{}", sheet);
+        assert!(styles.contains(r#"formatCode="0.0%""#), "{}", styles);
+    }
+
+    #[test]
+    fn test_fraction_scale_writes_percentages_as_sent() {
+        let data = percent_data(Some(PercentScale::Fraction));
+
+        let bytes = ExcelExporter::new().export(&data).unwrap();
+        let sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+
+        assert!(sheet.contains(r#"<c r="B2" s="1"><v>12.5</v></c>"#), "{}", sheet);
+        // A trailing percent sign still marks a whole percent
+        assert!(sheet.contains(r#"<c r="B3" s="1"><v>0.07</v></c>"#), "{}", sheet);
+    }
+
+    #[test]
+    fn test_literal_percent_format_keeps_whole_percents() {
+        let exporter = ExcelExporter::new().with_number_formats(ExcelNumberFormats {
+            percentage: r#"0.00"%""#.to_string(),
+            ..Default::default()
+        });
+
+        let bytes = exporter.export(&percent_data(None)).unwrap();
+        let sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+
+        assert!(sheet.contains(r#"<c r="B2" s="1"><v>12.5</v></c>"#), "{}", sheet);
+        assert!(sheet.contains(r#"<c r="B3" s="1"><v>7</v></c>"#), "{}", sheet);
+        assert!(shows_literal_percent(r"0.0\%"));
+        assert!(!shows_literal_percent("0.0%"));
+        assert!(!shows_literal_percent(r#"0.0%" %""#));
+    }
+
+    fn dated_data() -> ExportData {
+        ExportData {
+            headers: vec!["Event".to_string(), "Date".to_string()],
+            rows: vec![
+                vec!["Launch".to_string(), "2024-01-25".to_string()],
+                vec!["Unknown".to_string(), "soon".to_string()],
+            ],
+            column_metadata: Some(vec![ColumnMetadata::text(), ColumnMetadata::date()]),
+            ..sample_data()
+        }
+    }
+
+    #[test]
+    fn test_date_column_is_written_as_formatted_date_serial() {
+        let bytes = ExcelExporter::new().export(&dated_data()).unwrap();
+        let sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+        let styles = read_part(&bytes, "xl/styles.xml");
+
+        // 2024-01-25 is serial 45316; unparseable dates stay strings
+        assert!(sheet.contains(r#"<c r="B2" s="1"><v>45316</v></c>"#), "{}", sheet);
+        assert!(sheet.contains(r#"<c r="B3" t="s">"#), "{}", sheet);
+        assert!(styles.contains(r#"formatCode="yyyy-mm-dd""#), "{}", styles);
+    }
+
+    #[test]
+    fn test_date_input_format_parses_excel_date_serials() {
+        let data = ExportData {
+            rows: vec![vec!["Launch".to_string(), "25/01/2024".to_string()]],
+            options: Some(ExportOptions {
+                date_input_format: Some("%d/%m/%Y".to_string()),
+                date_output_format: Some("%B %-d, %Y".to_string()),
+                ..Default::default()
+            }),
+            ..dated_data()
+        };
+
+        let bytes = ExcelExporter::new().export(&data).unwrap();
+        let sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+
+        // Excel keeps its own date number format, whatever the output format
+        assert!(sheet.contains(r#"<c r="B2" s="1"><v>45316</v></c>"#), "{}", sheet);
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn test_date_output_format_applies_in_csv() {
+        use crate::infrastructure::exporters::CsvExporter;
+
+        let data = ExportData {
+            format: ExportFormat::Csv,
+            options: Some(ExportOptions {
+                date_output_format: Some("%d/%m/%Y".to_string()),
+                ..Default::default()
+            }),
+            ..dated_data()
+        };
+        let csv = CsvExporter.export(&data).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "Event,Date\nLaunch,25/01/2024\nUnknown,soon\n"
+        );
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn test_same_date_is_iso_in_csv() {
+        use crate::infrastructure::exporters::CsvExporter;
+
+        let data = ExportData {
+            format: ExportFormat::Csv,
+            ..dated_data()
+        };
+        let csv = CsvExporter.export(&data).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "Event,Date\nLaunch,2024-01-25\nUnknown,soon\n"
+        );
+    }
+
+    #[test]
+    fn test_summary_row_is_bold_after_data() {
+        let data = ExportData {
+            headers: vec!["Item".to_string(), "Price".to_string()],
+            rows: vec![
+                vec!["Widget".to_string(), "5.50".to_string()],
+                vec!["Gadget".to_string(), "150".to_string()],
+            ],
+            column_metadata: Some(vec![ColumnMetadata::text(), ColumnMetadata::currency()]),
+            summary_row: Some(vec!["Total".to_string(), "155.50".to_string()]),
+            ..sample_data()
+        };
+
+        let bytes = ExcelExporter::new().export(&data).unwrap();
+        let sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+        let styles = read_part(&bytes, "xl/styles.xml");
+
+        assert!(sheet.contains(r#"<c r="A4" s="2" t="s">"#), "{}", sheet);
+        assert!(sheet.contains(r#"<c r="B4" s="3"><v>155.5</v></c>"#), "{}", sheet);
+        assert!(styles.contains("<b/>"), "{}", styles);
+    }
+
+    #[test]
+    fn test_group_header_rows_are_merged_and_bold() {
+        let data = ExportData {
+            rows: vec![
+                vec!["001".to_string(), "Bolt".to_string()],
+                vec!["002".to_string(), "Nut".to_string()],
+            ],
+            options: Some(ExportOptions {
+                group_by: Some(ColumnRef::Index(1)),
+                ..Default::default()
+            }),
+            ..sample_data()
+        };
+
+        let bytes = ExcelExporter::new().export(&data).unwrap();
+        let sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+        let styles = read_part(&bytes, "xl/styles.xml");
+
+        // Rows 2 and 4 are group headers spanning both columns; data follows each
+        assert!(sheet.contains(r#"<mergeCell ref="A2:B2"/>"#), "{}", sheet);
+        assert!(sheet.contains(r#"<mergeCell ref="A4:B4"/>"#), "{}", sheet);
+        assert!(sheet.contains(r#"<c r="A5" t="s">"#), "{}", sheet);
+        assert!(styles.contains("<b/>"), "{}", styles);
+    }
+
+    #[test]
+    fn test_header_bold_formats_header_row_only() {
+        let data = ExportData {
+            options: Some(ExportOptions {
+                header_bold: Some(true),
+                ..Default::default()
+            }),
+            ..sample_data()
+        };
+
+        let bytes = ExcelExporter::new().export(&data).unwrap();
+        assert_eq!(&bytes[..2], b"PK");
+        let sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+        let styles = read_part(&bytes, "xl/styles.xml");
+
+        assert!(sheet.contains(r#"<c r="A1" s="1" t="s">"#), "{}", sheet);
+        assert!(sheet.contains(r#"<c r="B2" t="s">"#), "{}", sheet);
+        assert!(styles.contains("<b/>"), "{}", styles);
+
+        let plain = ExcelExporter::new().export(&sample_data()).unwrap();
+        let sheet = read_part(&plain, "xl/worksheets/sheet1.xml");
+        assert!(sheet.contains(r#"<c r="A1" t="s">"#), "{}", sheet);
+    }
+
+    fn with_background(color: &str) -> ExportData {
+        ExportData {
+            options: Some(ExportOptions {
+                header_background: Some(color.to_string()),
+                ..Default::default()
+            }),
+            ..sample_data()
+        }
+    }
+
+    #[test]
+    fn test_header_background_accepts_optional_hash() {
+        for color in ["#4472C4", "4472c4"] {
+            let bytes = ExcelExporter::new().export(&with_background(color)).unwrap();
+            let sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+            let styles = read_part(&bytes, "xl/styles.xml");
+
+            assert!(styles.contains(r#"<fgColor rgb="FF4472C4"/>"#), "{}", styles);
+            assert!(sheet.contains(r#"<c r="A1" s="1" t="s">"#), "{}", sheet);
+            assert!(sheet.contains(r#"<c r="A2" t="s">"#), "{}", sheet);
+        }
+    }
+
+    #[test]
+    fn test_malformed_header_background_is_an_error() {
+        for color in ["#44GG00", "#4472C", "blue", ""] {
+            let err = ExcelExporter::new()
+                .export(&with_background(color))
+                .unwrap_err();
+            assert!(err.to_string().contains("header_background"), "{}", err);
+        }
+    }
+
+    fn auto_fit_data(auto_fit: Option<bool>) -> ExportData {
+        ExportData {
+            headers: vec!["Id".to_string(), "Description".to_string()],
+            rows: vec![
+                vec!["1".to_string(), "A fairly long description of the item".to_string()],
+                vec!["2".to_string(), "x".repeat(200)],
+            ],
+            options: Some(ExportOptions {
+                auto_fit_columns: auto_fit,
+                ..Default::default()
+            }),
+            ..sample_data()
+        }
+    }
+
+    #[test]
+    fn test_auto_fit_widths_follow_longest_cell_within_bounds() {
+        assert_eq!(column_widths(auto_fit_data(Some(true)).table()), vec![8, 80]);
+        let data = ExportData {
+            rows: vec![vec!["1".to_string(), "Short".to_string()]],
+            ..auto_fit_data(Some(true))
+        };
+        assert_eq!(column_widths(data.table()), vec![8, 11]);
+    }
+
+    #[test]
+    fn test_auto_fit_columns_sets_sheet_widths() {
+        let fitted = ExcelExporter::new().export(&auto_fit_data(Some(true))).unwrap();
+        let fixed = ExcelExporter::new().export(&auto_fit_data(None)).unwrap();
+        let col_widths = |bytes: &[u8]| -> Vec<String> {
+            read_part(bytes, "xl/worksheets/sheet1.xml")
+                .split(" width=\"")
+                .skip(1)
+                .map(|rest| rest.split('"').next().unwrap().to_string())
+                .collect()
+        };
+
+        let fitted = col_widths(&fitted);
+        assert_eq!(fitted.len(), 2);
+        assert_ne!(fitted[0], fitted[1]);
+        // Equal widths are written as one <col> spanning both columns
+        assert_eq!(col_widths(&fixed).len(), 1);
+    }
+
+    #[test]
+    fn test_autofilter_covers_header_and_data_rows() {
+        let filtered = |rows: Vec<Vec<String>>| {
+            let data = ExportData {
+                headers: vec!["A".to_string(), "B".to_string(), "C".to_string()],
+                rows,
+                options: Some(ExportOptions {
+                    autofilter: Some(true),
+                    ..Default::default()
+                }),
+                ..sample_data()
+            };
+            read_part(&ExcelExporter::new().export(&data).unwrap(), "xl/worksheets/sheet1.xml")
+        };
+        let row = |n: &str| vec![n.to_string(), "x".to_string(), "y".to_string()];
+
+        let sheet = filtered(vec![row("1"), row("2")]);
+        assert!(sheet.contains(r#"<autoFilter ref="A1:C3"/>"#), "{}", sheet);
+        let sheet = filtered(Vec::new());
+        assert!(sheet.contains(r#"<autoFilter ref="A1:C1"/>"#), "{}", sheet);
+    }
+
+    fn sheet(title: &str, header: &str, value: &str) -> Sheet {
+        Sheet {
+            title: title.to_string(),
+            headers: vec![header.to_string()],
+            rows: vec![vec![value.to_string()]],
+            column_metadata: None,
+            summary_row: None,
+        }
+    }
+
+    #[test]
+    fn test_sheets_become_named_worksheets() {
+        let data = ExportData {
+            headers: Vec::new(),
+            rows: Vec::new(),
+            sheets: Some(vec![
+                sheet("Orders", "Order", "A-100"),
+                sheet("Customers", "Customer", "Ann"),
+            ]),
+            ..sample_data()
+        };
+
+        let bytes = ExcelExporter::new().export(&data).unwrap();
+        let workbook = read_part(&bytes, "xl/workbook.xml");
+        let shared_strings = read_part(&bytes, "xl/sharedStrings.xml");
+
+        assert!(workbook.contains(r#"<sheet name="Orders" sheetId="1""#), "{}", workbook);
+        assert!(workbook.contains(r#"<sheet name="Customers" sheetId="2""#), "{}", workbook);
+        assert!(read_part(&bytes, "xl/worksheets/sheet2.xml").contains(r#"<c r="A2""#));
+        for text in ["Order", "A-100", "Customer", "Ann"] {
+            assert!(shared_strings.contains(&format!("<t>{}</t>", text)), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_invalid_sheet_name_is_an_error() {
+        let data = ExportData {
+            sheets: Some(vec![sheet("Q1/Q2", "Order", "A-100")]),
+            ..sample_data()
+        };
+
+        assert!(ExcelExporter::new().export(&data).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+use std::fmt::Write;
+use crate::application::ports::ExportService;
+use crate::domain::dates::DateFormatter;
+use crate::domain::grouping::{grouped_rows, GroupedRow};
+use crate::domain::models::{ColumnType, ExportData};
+
+/// Exports rows as a standalone HTML document holding a single `<table>`
+pub struct HtmlExporter;
+
+/// Inline style of cells in number, currency and percentage columns
+const RIGHT_ALIGN: &str = r#" style="text-align: right""#;
+
+/// Escape text for use in element content and double-quoted attributes
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Write one `<tr>`, right-aligning the numeric columns and rendering `Date` cells
+/// with `dates` when given (data rows only)
+fn write_row(
+    html: &mut String,
+    data: &ExportData,
+    cells: &[String],
+    tag: &str,
+    dates: Option<&DateFormatter>,
+) {
+    html.push_str("<tr>");
+    for (col_idx, cell) in cells.iter().enumerate() {
+        let column_type = data.column_type(col_idx);
+        let style = if column_type.is_right_aligned() {
+            RIGHT_ALIGN
+        } else {
+            ""
+        };
+        let text = match dates {
+            Some(dates) if column_type == ColumnType::Date => dates.format_cell(cell),
+            _ => cell.clone(),
+        };
+        let _ = write!(html, "<{}{}>{}</{}>", tag, style, escape_html(&text), tag);
+    }
+    html.push_str("</tr>\n");
+}
+
+impl ExportService for HtmlExporter {
+    fn export(&self, data: &ExportData) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let include_header = data
+            .options
+            .as_ref()
+            .and_then(|o| o.include_header_row)
+            .unwrap_or(true);
+        let dates = DateFormatter::from_options(data.options.as_ref());
+
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        let _ = writeln!(html, "<title>{}</title>", escape_html(&data.title));
+        html.push_str("</head>\n<body>\n<table>\n");
+
+        if include_header {
+            html.push_str("<thead>\n");
+            write_row(&mut html, data, &data.headers, "th", None);
+            html.push_str("</thead>\n");
+        }
+
+        // A group header spans every column
+        html.push_str("<tbody>\n");
+        for entry in grouped_rows(data) {
+            match entry {
+                GroupedRow::GroupHeader(name) => {
+                    let _ = writeln!(
+                        html,
+                        "<tr><th colspan=\"{}\">{}</th></tr>",
+                        data.headers.len(),
+                        escape_html(name)
+                    );
+                }
+                GroupedRow::Data(row) => write_row(&mut html, data, row, "td", dates.as_ref()),
+            }
+        }
+        html.push_str("</tbody>\n");
+
+        if let Some(summary) = &data.summary_row {
+            html.push_str("<tfoot>\n");
+            write_row(&mut html, data, summary, "td", None);
+            html.push_str("</tfoot>\n");
+        }
+
+        html.push_str("</table>\n</body>\n</html>\n");
+        Ok(html.into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{ColumnMetadata, ExportFormat, ExportOptions};
+
+    fn sample_data(options: Option<ExportOptions>) -> ExportData {
+        ExportData {
+            title: "Orders & Returns".to_string(),
+            format: ExportFormat::Html,
+            headers: vec!["Item".to_string(), "Price".to_string()],
+            rows: vec![
+                vec!["<script>alert('x')</script>".to_string(), "9.50".to_string()],
+                vec!["Tea \"Earl Grey\"".to_string(), "3".to_string()],
+            ],
+            options,
+            column_metadata: Some(vec![ColumnMetadata::text(), ColumnMetadata::number()]),
+            summary_row: None,
+            sheets: None,
+        }
+    }
+
+    fn export(data: &ExportData) -> String {
+        String::from_utf8(HtmlExporter.export(data).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_cell_content_is_escaped() {
+        let html = export(&sample_data(None));
+
+        assert!(html.contains("<td>&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt;</td>"));
+        assert!(html.contains("<td>Tea &quot;Earl Grey&quot;</td>"));
+        assert!(html.contains("<title>Orders &amp; Returns</title>"));
+        assert!(!html.contains("<script>"));
+    }
+
+    #[test]
+    fn test_table_has_header_and_data_rows() {
+        let html = export(&sample_data(None));
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert_eq!(html.matches("<tr>").count(), 3);
+        assert!(html.contains(
+            "<thead>\n<tr><th>Item</th><th style=\"text-align: right\">Price</th></tr>"
+        ));
+        assert!(html.contains("<td style=\"text-align: right\">9.50</td>"));
+    }
+
+    #[test]
+    fn test_header_row_can_be_omitted() {
+        let options = Some(ExportOptions {
+            include_header_row: Some(false),
+            ..Default::default()
+        });
+
+        let html = export(&sample_data(options));
+
+        assert!(!html.contains("<thead>"));
+        assert_eq!(html.matches("<tr>").count(), 2);
+    }
+
+    #[test]
+    fn test_summary_row_goes_in_tfoot() {
+        let data = ExportData {
+            summary_row: Some(vec!["Total".to_string(), "12.50".to_string()]),
+            ..sample_data(None)
+        };
+
+        let html = export(&data);
+
+        assert_eq!(html.matches("<tr>").count(), 4);
+        assert!(html.contains("<tfoot>\n<tr><td>Total</td>"));
+    }
+
+    #[test]
+    fn test_date_cells_follow_date_options() {
+        let data = ExportData {
+            headers: vec!["Item".to_string(), "Shipped".to_string()],
+            rows: vec![vec!["Tea".to_string(), "2024-01-25".to_string()]],
+            column_metadata: Some(vec![ColumnMetadata::text(), ColumnMetadata::date()]),
+            ..sample_data(Some(ExportOptions {
+                date_output_format: Some("%d/%m/%Y".to_string()),
+                ..Default::default()
+            }))
+        };
+
+        let html = export(&data);
+
+        assert!(html.contains("<td>25/01/2024</td>"), "{}", html);
+    }
+
+    #[test]
+    fn test_group_headers_span_the_table() {
+        let data = ExportData {
+            rows: vec![
+                vec!["Tea".to_string(), "3".to_string()],
+                vec!["Tea".to_string(), "4".to_string()],
+                vec!["<Coffee>".to_string(), "5".to_string()],
+            ],
+            ..sample_data(Some(ExportOptions {
+                group_by: Some(crate::domain::models::ColumnRef::Name("Item".to_string())),
+                ..Default::default()
+            }))
+        };
+
+        let html = export(&data);
+
+        let body = &html[html.find("<tbody>").unwrap()..html.find("</tbody>").unwrap()];
+        let rows: Vec<&str> = body.lines().skip(1).collect();
+        assert_eq!(
+            rows,
+            vec![
+                "<tr><th colspan=\"2\">Tea</th></tr>",
+                "<tr><td>Tea</td><td style=\"text-align: right\">3</td></tr>",
+                "<tr><td>Tea</td><td style=\"text-align: right\">4</td></tr>",
+                "<tr><th colspan=\"2\">&lt;Coffee&gt;</th></tr>",
+                "<tr><td>&lt;Coffee&gt;</td><td style=\"text-align: right\">5</td></tr>",
+            ]
+        );
+    }
+}
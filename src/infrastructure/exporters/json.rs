@@ -0,0 +1,254 @@
+use std::borrow::Cow;
+use serde::ser::{SerializeMap, Serializer};
+use serde::Serialize;
+use crate::application::ports::ExportService;
+use crate::domain::dates::{DateFormatter, ISO_DATE};
+use crate::domain::models::{ColumnType, ExportData, JsonLayout};
+
+/// Exports rows as a JSON array of objects keyed by header, in header order, or
+/// as an array of arrays whose first entry holds the headers
+///
+/// `Date` cells are always written as ISO-8601 (`YYYY-MM-DD`) so consumers can parse
+/// them: cells sent in `date_input_format` are converted, while `date_output_format`
+/// and the `locale` are ignored.
+pub struct JsonExporter;
+
+/// A cell, written as `null` when empty and `null_on_empty` is set
+fn cell_value(cell: &str, null_on_empty: bool) -> Option<&str> {
+    (!(null_on_empty && cell.is_empty())).then_some(cell)
+}
+
+/// Converts `Date` cells from the request's `date_input_format` to ISO; `None` when
+/// they are already sent as ISO
+fn iso_dates(data: &ExportData) -> Option<DateFormatter> {
+    let input = data.options.as_ref()?.date_input_format.as_deref()?;
+    Some(DateFormatter::new(input, ISO_DATE))
+}
+
+/// A row's cells, with `Date` cells converted by `dates`
+fn row_cells<'a>(
+    data: &ExportData,
+    cells: &'a [String],
+    dates: Option<&DateFormatter>,
+) -> Vec<Cow<'a, str>> {
+    cells
+        .iter()
+        .enumerate()
+        .map(|(col_idx, cell)| match dates {
+            Some(dates) if data.column_type(col_idx) == ColumnType::Date => {
+                Cow::Owned(dates.format_cell(cell))
+            }
+            _ => Cow::Borrowed(cell.as_str()),
+        })
+        .collect()
+}
+
+/// One row serialized as an object; keys follow the header order
+///
+/// Entries are written straight to the serializer rather than collected into a
+/// `serde_json::Map`, which sorts its keys unless `preserve_order` is enabled.
+struct JsonRow<'a> {
+    headers: &'a [String],
+    cells: Vec<Cow<'a, str>>,
+    null_on_empty: bool,
+}
+
+impl Serialize for JsonRow<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.headers.len()))?;
+        for (header, cell) in self.headers.iter().zip(&self.cells) {
+            map.serialize_entry(header, &cell_value(cell, self.null_on_empty))?;
+        }
+        map.end()
+    }
+}
+
+impl ExportService for JsonExporter {
+    fn export(&self, data: &ExportData) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let null_on_empty = data
+            .options
+            .as_ref()
+            .and_then(|o| o.json_null_on_empty)
+            .unwrap_or(false);
+        let layout = data.options.as_ref().and_then(|o| o.json_layout).unwrap_or_default();
+        let dates = iso_dates(data);
+
+        if layout == JsonLayout::Arrays {
+            let rows: Vec<Vec<Cow<str>>> = data
+                .rows
+                .iter()
+                .map(|cells| row_cells(data, cells, dates.as_ref()))
+                .collect();
+            let headers = data.headers.iter().map(|header| Some(header.as_str()));
+            let rows = rows.iter().map(|cells| {
+                cells
+                    .iter()
+                    .map(|cell| cell_value(cell, null_on_empty))
+                    .collect::<Vec<_>>()
+            });
+            let arrays: Vec<Vec<Option<&str>>> =
+                std::iter::once(headers.collect()).chain(rows).collect();
+            return Ok(serde_json::to_vec(&arrays)?);
+        }
+
+        let rows: Vec<JsonRow> = data
+            .rows
+            .iter()
+            .map(|cells| JsonRow {
+                headers: &data.headers,
+                cells: row_cells(data, cells, dates.as_ref()),
+                null_on_empty,
+            })
+            .collect();
+
+        Ok(serde_json::to_vec(&rows)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{ExportFormat, ExportOptions};
+
+    fn sample_data(options: Option<ExportOptions>) -> ExportData {
+        ExportData {
+            title: "People".to_string(),
+            format: ExportFormat::Json,
+            headers: vec!["Name".to_string(), "Email".to_string()],
+            rows: vec![
+                vec!["Ann".to_string(), "ann@example.com".to_string()],
+                vec!["Bob".to_string(), String::new()],
+            ],
+            options,
+            column_metadata: None,
+            summary_row: None,
+            sheets: None,
+        }
+    }
+
+    fn null_on_empty() -> Option<ExportOptions> {
+        Some(ExportOptions {
+            json_null_on_empty: Some(true),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_rows_become_objects_keyed_by_header() {
+        let bytes = JsonExporter.export(&sample_data(None)).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+        assert_eq!(
+            value,
+            serde_json::json!([
+                {"Name": "Ann", "Email": "ann@example.com"},
+                {"Name": "Bob", "Email": ""}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_keys_follow_header_order() {
+        let data = ExportData {
+            headers: vec!["Zeta".to_string(), "Alpha".to_string(), "Mid".to_string()],
+            rows: vec![vec!["1".to_string(), "2".to_string(), "3".to_string()]],
+            ..sample_data(None)
+        };
+
+        let bytes = JsonExporter.export(&data).unwrap();
+
+        // Compare the raw text: `serde_json::Value` would sort the keys
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            r#"[{"Zeta":"1","Alpha":"2","Mid":"3"}]"#
+        );
+    }
+
+    #[test]
+    fn test_empty_cells_become_null_when_configured() {
+        let bytes = JsonExporter.export(&sample_data(null_on_empty())).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+        assert_eq!(value[1]["Email"], serde_json::Value::Null);
+        assert_eq!(value[0]["Email"], "ann@example.com");
+    }
+
+    #[test]
+    fn test_arrays_layout_starts_with_headers() {
+        let options = Some(ExportOptions {
+            json_layout: Some(JsonLayout::Arrays),
+            json_null_on_empty: Some(true),
+            ..Default::default()
+        });
+
+        let bytes = JsonExporter.export(&sample_data(options)).unwrap();
+        let value: Vec<Vec<Option<String>>> = serde_json::from_slice(&bytes).unwrap();
+
+        let text = |cell: &str| Some(cell.to_string());
+        assert_eq!(
+            value,
+            vec![
+                vec![text("Name"), text("Email")],
+                vec![text("Ann"), text("ann@example.com")],
+                vec![text("Bob"), None],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_objects_round_trip_to_rows() {
+        let data = sample_data(None);
+
+        let bytes = JsonExporter.export(&data).unwrap();
+        let objects: Vec<serde_json::Map<String, serde_json::Value>> =
+            serde_json::from_slice(&bytes).unwrap();
+
+        let rows: Vec<Vec<String>> = objects
+            .iter()
+            .map(|object| {
+                data.headers
+                    .iter()
+                    .map(|header| object[header].as_str().unwrap().to_string())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(rows, data.rows);
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn test_same_empty_cell_is_blank_in_csv() {
+        let data = sample_data(null_on_empty());
+
+        let csv = super::super::CsvExporter.export(&data).unwrap();
+        let json = JsonExporter.export(&data).unwrap();
+
+        assert!(String::from_utf8(csv).unwrap().ends_with("Bob,\n"));
+        assert!(String::from_utf8(json).unwrap().contains(r#""Email":null"#));
+    }
+
+    #[test]
+    fn test_date_cells_are_written_as_iso() {
+        let data = |layout| ExportData {
+            headers: vec!["Name".to_string(), "Joined".to_string()],
+            rows: vec![vec!["Ann".to_string(), "25.01.2024".to_string()]],
+            column_metadata: Some(vec![
+                crate::domain::models::ColumnMetadata::text(),
+                crate::domain::models::ColumnMetadata::date(),
+            ]),
+            ..sample_data(Some(ExportOptions {
+                date_input_format: Some("%d.%m.%Y".to_string()),
+                date_output_format: Some("%d/%m/%Y".to_string()),
+                json_layout: Some(layout),
+                ..Default::default()
+            }))
+        };
+
+        let objects = JsonExporter.export(&data(JsonLayout::Objects)).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&objects).unwrap();
+        assert_eq!(value, serde_json::json!([{"Name": "Ann", "Joined": "2024-01-25"}]));
+        let arrays = JsonExporter.export(&data(JsonLayout::Arrays)).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&arrays).unwrap();
+        assert_eq!(value, serde_json::json!([["Name", "Joined"], ["Ann", "2024-01-25"]]));
+    }
+}
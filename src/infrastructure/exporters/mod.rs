@@ -1,7 +1,42 @@
+#[cfg(feature = "excel")]
 mod excel;
+#[cfg(feature = "csv")]
 mod csv;
+#[cfg(feature = "pdf")]
 mod pdf;
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "html")]
+mod html;
+mod disabled;
 
+#[cfg(feature = "excel")]
 pub use excel::ExcelExporter;
+#[cfg(feature = "csv")]
 pub use csv::CsvExporter;
-pub use pdf::PdfExporter;
+#[cfg(feature = "pdf")]
+pub use pdf::{
+    GroupingNumberFormatter, LatinTextFormatter, NumberFormatter, PdfExporter, PdfLayoutConfig,
+    TextFormatter,
+};
+#[cfg(feature = "json")]
+pub use json::JsonExporter;
+#[cfg(feature = "html")]
+pub use html::HtmlExporter;
+pub use disabled::DisabledExporter;
+
+use crate::domain::models::ExportFormat;
+
+/// Formats whose exporter is compiled into this build (see the Cargo features)
+pub fn enabled_formats() -> Vec<ExportFormat> {
+    ExportFormat::ALL
+        .into_iter()
+        .filter(|format| match format {
+            ExportFormat::Excel => cfg!(feature = "excel"),
+            ExportFormat::Csv | ExportFormat::Tsv => cfg!(feature = "csv"),
+            ExportFormat::Pdf => cfg!(feature = "pdf"),
+            ExportFormat::Json => cfg!(feature = "json"),
+            ExportFormat::Html => cfg!(feature = "html"),
+        })
+        .collect()
+}
@@ -0,0 +1,308 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use dashmap::DashMap;
+use uuid::Uuid;
+use crate::domain::models::ExportData;
+use crate::infrastructure::checksum::sha256_hex;
+
+/// How often `spawn_cleanup` is run by the server
+pub const CLEANUP_PERIOD: Duration = Duration::from_secs(60);
+
+/// Failure message of a job still pending when its deadline passes
+pub const TIMED_OUT: &str = "Export timed out";
+
+/// Finished export waiting to be downloaded
+#[derive(Debug, PartialEq)]
+pub struct JobOutput {
+    pub bytes: Vec<u8>,
+    pub mime: &'static str,
+    /// `Content-Disposition` value, with the filename chosen at submission
+    pub disposition: String,
+    /// Hex SHA-256 of `bytes`, computed when the job is finished so a download can be
+    /// checked against what was stored
+    pub checksum: String,
+    /// Titles of the sheets deduping dropped, reported when the job is downloaded
+    pub omitted_sheets: Vec<String>,
+    /// Lossy fixes made to the data, reported when the job is downloaded
+    pub warnings: Vec<String>,
+}
+
+impl JobOutput {
+    /// Output of a job whose checksum `JobStore::finish` has yet to compute
+    pub fn new(bytes: Vec<u8>, mime: &'static str, disposition: String) -> Self {
+        Self {
+            bytes,
+            mime,
+            disposition,
+            checksum: String::new(),
+            omitted_sheets: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// State of an asynchronous export job
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobStatus {
+    Pending,
+    Done(Arc<JobOutput>),
+    /// The export failed with this message
+    Failed(String),
+}
+
+struct Job {
+    /// Subject of the principal that submitted the job; only it may poll the job
+    subject: Option<String>,
+    /// Data the job was submitted with, kept so it can be exported again in another format
+    source: Arc<ExportData>,
+    status: JobStatus,
+    created_at: Instant,
+    finished_at: Option<Instant>,
+}
+
+/// In-memory store of asynchronous export jobs
+///
+/// At most `capacity` jobs are held at once. A job still pending `timeout` after it was
+/// created fails as timed out; finished jobs are kept for `ttl` so their result can be
+/// fetched, then dropped.
+pub struct JobStore {
+    ttl: Duration,
+    timeout: Duration,
+    capacity: usize,
+    jobs: DashMap<Uuid, Job>,
+}
+
+impl JobStore {
+    pub fn new(ttl: Duration, timeout: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            timeout,
+            capacity,
+            jobs: DashMap::new(),
+        }
+    }
+
+    /// Register a pending job exporting `source`, submitted by `subject`, and return its id
+    ///
+    /// `None` when the store already holds `capacity` unexpired jobs.
+    pub fn create(&self, subject: Option<&str>, source: ExportData) -> Option<Uuid> {
+        self.create_at(subject, source, Instant::now())
+    }
+
+    fn create_at(&self, subject: Option<&str>, source: ExportData, now: Instant) -> Option<Uuid> {
+        if self.jobs.len() >= self.capacity {
+            self.purge_expired_at(now);
+            if self.jobs.len() >= self.capacity {
+                return None;
+            }
+        }
+        let id = Uuid::new_v4();
+        self.jobs.insert(
+            id,
+            Job {
+                subject: subject.map(str::to_string),
+                source: Arc::new(source),
+                status: JobStatus::Pending,
+                created_at: now,
+                finished_at: None,
+            },
+        );
+        Some(id)
+    }
+
+    /// Record the outcome of a job, checksumming its output as it is stored
+    ///
+    /// Ignored once the job has timed out, so a late result cannot replace the failure.
+    pub fn finish(&self, id: Uuid, result: Result<JobOutput, String>) {
+        if let Some(mut job) = self.jobs.get_mut(&id) {
+            self.time_out(&mut job, Instant::now());
+            if job.status != JobStatus::Pending {
+                return;
+            }
+            job.status = match result {
+                Ok(output) => JobStatus::Done(Arc::new(JobOutput {
+                    checksum: sha256_hex(&output.bytes),
+                    ..output
+                })),
+                Err(message) => JobStatus::Failed(message),
+            };
+            job.finished_at = Some(Instant::now());
+        }
+    }
+
+    /// Status of a job submitted by `subject`
+    ///
+    /// `None` when the id is unknown, the job has expired or another subject submitted it.
+    pub fn status(&self, id: Uuid, subject: Option<&str>) -> Option<JobStatus> {
+        self.status_at(id, subject, Instant::now())
+    }
+
+    fn status_at(&self, id: Uuid, subject: Option<&str>, now: Instant) -> Option<JobStatus> {
+        let mut job = self.jobs.get_mut(&id)?;
+        self.time_out(&mut job, now);
+        if job.subject.as_deref() != subject || self.is_expired(&job, now) {
+            return None;
+        }
+        Some(job.status.clone())
+    }
+
+    /// Source data of a job submitted by `subject`, under the same rules as `status`
+    pub fn source(&self, id: Uuid, subject: Option<&str>) -> Option<Arc<ExportData>> {
+        let now = Instant::now();
+        let mut job = self.jobs.get_mut(&id)?;
+        self.time_out(&mut job, now);
+        if job.subject.as_deref() != subject || self.is_expired(&job, now) {
+            return None;
+        }
+        Some(job.source.clone())
+    }
+
+    /// Overwrite a finished job's stored bytes, keeping its checksum, to simulate corruption
+    #[cfg(test)]
+    pub(crate) fn corrupt_output(&self, id: Uuid, bytes: Vec<u8>) {
+        if let Some(mut job) = self.jobs.get_mut(&id) {
+            if let JobStatus::Done(output) = &job.status {
+                let corrupted = JobOutput {
+                    bytes,
+                    mime: output.mime,
+                    disposition: output.disposition.clone(),
+                    checksum: output.checksum.clone(),
+                    omitted_sheets: output.omitted_sheets.clone(),
+                    warnings: output.warnings.clone(),
+                };
+                job.status = JobStatus::Done(Arc::new(corrupted));
+            }
+        }
+    }
+
+    /// Time out overdue pending jobs and drop finished jobs older than the TTL
+    pub fn purge_expired(&self) {
+        self.purge_expired_at(Instant::now());
+    }
+
+    fn purge_expired_at(&self, now: Instant) {
+        self.jobs.retain(|_, job| {
+            self.time_out(job, now);
+            !self.is_expired(job, now)
+        });
+    }
+
+    /// Fail a job that is still pending past its deadline
+    fn time_out(&self, job: &mut Job, now: Instant) {
+        if job.status == JobStatus::Pending && now.duration_since(job.created_at) >= self.timeout {
+            job.status = JobStatus::Failed(TIMED_OUT.to_string());
+            job.finished_at = Some(now);
+        }
+    }
+
+    fn is_expired(&self, job: &Job, now: Instant) -> bool {
+        job.finished_at
+            .is_some_and(|finished| now.duration_since(finished) >= self.ttl)
+    }
+
+    /// Purge expired jobs every `period` until the store is dropped
+    pub fn spawn_cleanup(self: &Arc<Self>, period: Duration) -> tokio::task::JoinHandle<()> {
+        let store = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                match store.upgrade() {
+                    Some(store) => store.purge_expired(),
+                    None => break,
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source() -> ExportData {
+        ExportData::builder()
+            .format(crate::domain::models::ExportFormat::Csv)
+            .headers(["Name"])
+            .add_row(["Widget"])
+            .build()
+            .unwrap()
+    }
+
+    fn output() -> JobOutput {
+        JobOutput::new(
+            b"Name\nWidget\n".to_vec(),
+            "text/csv",
+            "attachment; filename=\"report.csv\"".to_string(),
+        )
+    }
+
+    fn store() -> JobStore {
+        JobStore::new(Duration::from_secs(60), Duration::from_secs(300), 10)
+    }
+
+    #[test]
+    fn test_job_is_pending_until_finished() {
+        let store = store();
+        let id = store.create(Some("web-client"), source()).unwrap();
+
+        assert_eq!(store.status(id, Some("web-client")), Some(JobStatus::Pending));
+        store.finish(id, Ok(output()));
+        let Some(JobStatus::Done(done)) = store.status(id, Some("web-client")) else {
+            panic!("job is not done");
+        };
+        assert_eq!(done.bytes, output().bytes);
+        assert_eq!(done.checksum, sha256_hex(&output().bytes));
+        // Other subjects cannot see the job
+        assert_eq!(store.status(id, Some("someone-else")), None);
+        assert_eq!(store.status(Uuid::new_v4(), Some("web-client")), None);
+        assert_eq!(store.source(id, Some("web-client")).unwrap().rows, source().rows);
+        assert!(store.source(id, Some("someone-else")).is_none());
+    }
+
+    #[test]
+    fn test_finished_jobs_expire_after_ttl() {
+        let store = store();
+        let pending = store.create(None, source()).unwrap();
+        let failed = store.create(None, source()).unwrap();
+        store.finish(failed, Err("Export failed".to_string()));
+
+        let later = Instant::now() + Duration::from_secs(61);
+        assert_eq!(store.status_at(failed, None, later), None);
+        store.purge_expired_at(later);
+
+        assert_eq!(store.jobs.len(), 1);
+        assert_eq!(store.status_at(pending, None, later), Some(JobStatus::Pending));
+    }
+
+    #[test]
+    fn test_pending_jobs_time_out() {
+        let store = store();
+        let id = store.create(None, source()).unwrap();
+        let created = Instant::now();
+
+        let overdue = created + Duration::from_secs(301);
+        let timed_out = JobStatus::Failed(TIMED_OUT.to_string());
+        assert_eq!(store.status_at(id, None, overdue), Some(timed_out.clone()));
+        // A result arriving after the deadline does not replace the failure
+        store.finish(id, Ok(output()));
+        assert_eq!(store.status(id, None), Some(timed_out));
+
+        // The timed out job is then kept for the TTL like any finished job
+        store.purge_expired_at(overdue + Duration::from_secs(61));
+        assert!(store.jobs.is_empty());
+    }
+
+    #[test]
+    fn test_create_refuses_jobs_beyond_capacity() {
+        let store = JobStore::new(Duration::from_secs(60), Duration::from_secs(300), 2);
+        let first = store.create(None, source()).unwrap();
+        store.create(None, source()).unwrap();
+        assert_eq!(store.create(None, source()), None);
+
+        // Expired jobs are purged to make room
+        store.finish(first, Ok(output()));
+        let later = Instant::now() + Duration::from_secs(61);
+        assert!(store.create_at(None, source(), later).is_some());
+    }
+}
@@ -0,0 +1,122 @@
+use std::time::Duration;
+use metrics::{counter, describe_counter, describe_histogram, histogram, Unit};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle, PrometheusRecorder};
+use crate::application::ports::ExportRecorder;
+use crate::domain::models::ExportFormat;
+
+/// Upper bounds, in seconds, of the export duration histogram buckets
+const DURATION_BUCKETS: [f64; 10] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 10.0];
+
+const EXPORTS_TOTAL: &str = "exports_total";
+const EXPORT_FAILURES_TOTAL: &str = "export_failures_total";
+const EXPORT_DURATION_SECONDS: &str = "export_duration_seconds";
+const EXPORT_REPAIRS_TOTAL: &str = "export_repairs_total";
+
+/// Export counters and durations, rendered in the Prometheus text format
+///
+/// Each instance owns its Prometheus recorder and records through it as a local
+/// recorder, so several app states (e.g. in tests) never share or fight over the
+/// process-wide one.
+pub struct ExportMetrics {
+    recorder: PrometheusRecorder,
+    handle: PrometheusHandle,
+}
+
+impl Default for ExportMetrics {
+    fn default() -> Self {
+        let recorder = PrometheusBuilder::new()
+            .set_buckets_for_metric(
+                Matcher::Full(EXPORT_DURATION_SECONDS.to_string()),
+                &DURATION_BUCKETS,
+            )
+            .expect("duration buckets are not empty")
+            .build_recorder();
+        let handle = recorder.handle();
+        let metrics = Self { recorder, handle };
+        metrics::with_local_recorder(&metrics.recorder, || {
+            describe_counter!(EXPORTS_TOTAL, "Exports completed, by format");
+            describe_counter!(EXPORT_FAILURES_TOTAL, "Exports that failed, by format");
+            describe_counter!(
+                EXPORT_REPAIRS_TOTAL,
+                "Lossy fixes made to export data instead of rejecting it, by format"
+            );
+            describe_histogram!(
+                EXPORT_DURATION_SECONDS,
+                Unit::Seconds,
+                "Time from validation to finished output"
+            );
+            // Every format is listed from the start, at zero
+            for format in ExportFormat::ALL {
+                counter!(EXPORTS_TOTAL, "format" => format.name()).increment(0);
+                counter!(EXPORT_FAILURES_TOTAL, "format" => format.name()).increment(0);
+                counter!(EXPORT_REPAIRS_TOTAL, "format" => format.name()).increment(0);
+            }
+        });
+        metrics
+    }
+}
+
+impl ExportMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Render every metric in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        self.handle.render()
+    }
+}
+
+impl ExportRecorder for ExportMetrics {
+    fn record(&self, format: ExportFormat, duration: Duration, succeeded: bool) {
+        let name = if succeeded { EXPORTS_TOTAL } else { EXPORT_FAILURES_TOTAL };
+        metrics::with_local_recorder(&self.recorder, || {
+            counter!(name, "format" => format.name()).increment(1);
+            histogram!(EXPORT_DURATION_SECONDS).record(duration.as_secs_f64());
+        });
+    }
+
+    fn repaired(&self, format: ExportFormat, _warning: &str) {
+        metrics::with_local_recorder(&self.recorder, || {
+            counter!(EXPORT_REPAIRS_TOTAL, "format" => format.name()).increment(1);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_counts_exports_and_buckets_durations() {
+        let metrics = ExportMetrics::new();
+        metrics.record(ExportFormat::Csv, Duration::from_millis(20), true);
+        metrics.record(ExportFormat::Csv, Duration::from_millis(300), false);
+
+        let text = metrics.render();
+
+        assert!(text.contains("# TYPE exports_total counter\n"), "{}", text);
+        assert!(text.contains("exports_total{format=\"csv\"} 1\n"), "{}", text);
+        assert!(text.contains("exports_total{format=\"pdf\"} 0\n"), "{}", text);
+        assert!(text.contains("export_failures_total{format=\"csv\"} 1\n"), "{}", text);
+        assert!(text.contains("# TYPE export_duration_seconds histogram\n"), "{}", text);
+        assert!(text.contains("export_duration_seconds_bucket{le=\"0.01\"} 0\n"), "{}", text);
+        assert!(text.contains("export_duration_seconds_bucket{le=\"0.025\"} 1\n"), "{}", text);
+        assert!(text.contains("export_duration_seconds_bucket{le=\"0.5\"} 2\n"), "{}", text);
+        assert!(text.contains("export_duration_seconds_bucket{le=\"+Inf\"} 2\n"), "{}", text);
+        assert!(text.contains("export_duration_seconds_count 2\n"), "{}", text);
+    }
+
+    #[test]
+    fn test_repairs_are_counted_by_format() {
+        let metrics = ExportMetrics::new();
+        metrics.repaired(ExportFormat::Csv, "truncated 6-byte cell in row 1 to 4 bytes");
+        metrics.repaired(ExportFormat::Csv, "truncated 5-byte cell in row 2 to 4 bytes");
+
+        let text = metrics.render();
+
+        assert!(text.contains("# TYPE export_repairs_total counter\n"), "{}", text);
+        assert!(text.contains("export_repairs_total{format=\"csv\"} 2\n"), "{}", text);
+        assert!(text.contains("export_repairs_total{format=\"pdf\"} 0\n"), "{}", text);
+    }
+}
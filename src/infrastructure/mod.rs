@@ -1,2 +1,8 @@
 pub mod exporters;
 pub mod auth;
+pub mod checksum;
+pub mod archive;
+pub mod rate_limit;
+pub mod upload;
+pub mod jobs;
+pub mod metrics;
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Outcome of a rate limit check, with the quota left in the current window
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    /// Seconds until the current window ends and the quota resets
+    pub reset_after: u64,
+}
+
+/// Requests counted for one subject in its current window
+struct Window {
+    started: Instant,
+    count: u32,
+}
+
+/// Windows of the subjects seen recently
+struct Windows {
+    by_subject: HashMap<String, Window>,
+    /// When windows that had already ended were last dropped
+    pruned: Instant,
+}
+
+/// Fixed-window request limiter keyed by token subject (`sub`)
+///
+/// Windows that have ended are dropped at most once per window length, so the map
+/// only holds subjects seen in roughly the last two windows.
+pub struct RateLimiter {
+    limit: u32,
+    window: Duration,
+    windows: Mutex<Windows>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            windows: Mutex::new(Windows {
+                by_subject: HashMap::new(),
+                pruned: Instant::now(),
+            }),
+        }
+    }
+
+    /// Count a request for the subject and report whether it is allowed
+    pub fn check(&self, subject: &str) -> RateLimitDecision {
+        self.check_at(subject, Instant::now())
+    }
+
+    fn check_at(&self, subject: &str, now: Instant) -> RateLimitDecision {
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        if now.duration_since(windows.pruned) >= self.window {
+            windows
+                .by_subject
+                .retain(|_, window| now.duration_since(window.started) < self.window);
+            windows.pruned = now;
+        }
+        let window = windows.by_subject.entry(subject.to_string()).or_insert(Window {
+            started: now,
+            count: 0,
+        });
+        if now.duration_since(window.started) >= self.window {
+            window.started = now;
+            window.count = 0;
+        }
+
+        let allowed = window.count < self.limit;
+        if allowed {
+            window.count += 1;
+        }
+        let elapsed = now.duration_since(window.started);
+        RateLimitDecision {
+            allowed,
+            limit: self.limit,
+            remaining: self.limit - window.count,
+            reset_after: self.window.saturating_sub(elapsed).as_secs_f64().ceil() as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remaining_decrements_until_throttled() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60));
+        let now = Instant::now();
+
+        let first = limiter.check_at("alice", now);
+        let second = limiter.check_at("alice", now + Duration::from_secs(1));
+        let third = limiter.check_at("alice", now + Duration::from_secs(2));
+
+        assert!(first.allowed && second.allowed && !third.allowed);
+        assert_eq!((first.remaining, second.remaining, third.remaining), (1, 0, 0));
+        assert_eq!(third.limit, 2);
+        assert_eq!(third.reset_after, 58);
+        // Other subjects have their own quota
+        assert_eq!(limiter.check_at("bob", now).remaining, 1);
+    }
+
+    #[test]
+    fn test_quota_resets_after_window() {
+        let limiter = RateLimiter::new(1, Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert!(limiter.check_at("alice", now).allowed);
+        assert!(!limiter.check_at("alice", now + Duration::from_secs(59)).allowed);
+
+        let reset = limiter.check_at("alice", now + Duration::from_secs(60));
+        assert!(reset.allowed);
+        assert_eq!(reset.remaining, 0);
+        assert_eq!(reset.reset_after, 60);
+    }
+
+    #[test]
+    fn test_ended_windows_are_dropped() {
+        let limiter = RateLimiter::new(1, Duration::from_secs(60));
+        let now = Instant::now();
+
+        limiter.check_at("alice", now);
+        limiter.check_at("bob", now + Duration::from_secs(30));
+        assert_eq!(limiter.windows.lock().unwrap().by_subject.len(), 2);
+
+        // Alice's window has ended; Bob's is still running
+        limiter.check_at("carol", now + Duration::from_secs(61));
+        let windows = limiter.windows.lock().unwrap();
+        let mut subjects: Vec<&str> = windows.by_subject.keys().map(String::as_str).collect();
+        subjects.sort();
+        assert_eq!(subjects, vec!["bob", "carol"]);
+    }
+}
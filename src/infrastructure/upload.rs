@@ -0,0 +1,180 @@
+use std::time::Duration;
+use reqwest::redirect::Policy;
+use reqwest::{header, Client, Url};
+
+/// Longest an upload may take, from connecting to reading the response status
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// Longest to wait for the upload host to accept the connection
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Upload failures
+#[derive(Debug)]
+pub enum UploadError {
+    /// The URL is malformed, not http(s), or its host is not allowed
+    Rejected(String),
+    /// The PUT request could not be sent
+    Request(String),
+    /// The HTTP client could not be built
+    Client(String),
+}
+
+impl std::fmt::Display for UploadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rejected(msg) => write!(f, "Upload URL rejected: {}", msg),
+            Self::Request(msg) => write!(f, "Upload failed: {}", msg),
+            Self::Client(msg) => write!(f, "Upload client unavailable: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for UploadError {}
+
+/// PUTs exports to caller-provided (e.g. pre-signed) URLs on allowed hosts
+///
+/// Only hosts matching a configured pattern are contacted, and redirects are not
+/// followed, so callers cannot point the service at internal addresses. Patterns
+/// are exact hosts (`uploads.example.com`) or subdomain wildcards (`*.example.com`).
+pub struct Uploader {
+    client: Client,
+    allowed_hosts: Vec<String>,
+}
+
+impl Uploader {
+    pub fn new(allowed_hosts: Vec<String>) -> Result<Self, UploadError> {
+        // Falling back to a default client would silently follow redirects again
+        let client = Client::builder()
+            .redirect(Policy::none())
+            .timeout(REQUEST_TIMEOUT)
+            .connect_timeout(CONNECT_TIMEOUT)
+            .build()
+            .map_err(|e| UploadError::Client(e.to_string()))?;
+        Ok(Self {
+            client,
+            allowed_hosts: allowed_hosts
+                .into_iter()
+                .map(|host| host.trim().to_lowercase())
+                .filter(|host| !host.is_empty())
+                .collect(),
+        })
+    }
+
+    /// Parse the URL and check it against the allowed host patterns
+    pub fn check_url(&self, url: &str) -> Result<Url, UploadError> {
+        let url = Url::parse(url).map_err(|e| UploadError::Rejected(e.to_string()))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(UploadError::Rejected(format!(
+                "unsupported scheme: {}",
+                url.scheme()
+            )));
+        }
+        let host = url
+            .host_str()
+            .ok_or_else(|| UploadError::Rejected("URL has no host".to_string()))?
+            .to_lowercase();
+        if !self
+            .allowed_hosts
+            .iter()
+            .any(|pattern| host_matches(pattern, &host))
+        {
+            return Err(UploadError::Rejected(format!("host not allowed: {}", host)));
+        }
+        Ok(url)
+    }
+
+    /// PUT the bytes and return the upstream status code
+    pub async fn put(
+        &self,
+        url: &str,
+        bytes: Vec<u8>,
+        content_type: &str,
+    ) -> Result<u16, UploadError> {
+        let url = self.check_url(url)?;
+        let response = self
+            .client
+            .put(url)
+            .header(header::CONTENT_TYPE, content_type)
+            .body(bytes)
+            .send()
+            .await
+            .map_err(|e| UploadError::Request(e.to_string()))?;
+        Ok(response.status().as_u16())
+    }
+}
+
+fn host_matches(pattern: &str, host: &str) -> bool {
+    match pattern.strip_prefix("*.") {
+        Some(domain) => host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.len() > 1 && prefix.ends_with('.')),
+        None => pattern == host,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Bytes, http::HeaderMap, routing::put, Router};
+    use std::sync::{Arc, Mutex};
+
+    type Received = Arc<Mutex<Vec<(String, Vec<u8>)>>>;
+
+    /// Local server that records the content type and body of every PUT
+    async fn mock_server() -> (String, Received) {
+        let received: Received = Arc::default();
+        let log = received.clone();
+        let app = Router::new().route(
+            "/upload",
+            put(move |headers: HeaderMap, body: Bytes| async move {
+                let content_type = headers[header::CONTENT_TYPE].to_str().unwrap().to_string();
+                log.lock().unwrap().push((content_type, body.to_vec()));
+                axum::http::StatusCode::CREATED
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{}/upload", address), received)
+    }
+
+    #[test]
+    fn test_host_patterns() {
+        assert!(host_matches("uploads.example.com", "uploads.example.com"));
+        assert!(host_matches("*.example.com", "bucket.example.com"));
+        assert!(!host_matches("*.example.com", "example.com"));
+        assert!(!host_matches("*.example.com", "evilexample.com"));
+        assert!(!host_matches("example.com", "example.com.evil.io"));
+    }
+
+    #[tokio::test]
+    async fn test_put_sends_bytes_with_content_type() {
+        let (url, received) = mock_server().await;
+        let uploader = Uploader::new(vec!["127.0.0.1".to_string()]).unwrap();
+
+        let status = uploader
+            .put(&url, b"Name\nWidget\n".to_vec(), "text/csv")
+            .await
+            .unwrap();
+
+        assert_eq!(status, 201);
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].0, "text/csv");
+        assert_eq!(received[0].1, b"Name\nWidget\n");
+    }
+
+    #[tokio::test]
+    async fn test_disallowed_host_is_rejected() {
+        let (url, received) = mock_server().await;
+        let uploader = Uploader::new(vec!["*.example.com".to_string()]).unwrap();
+
+        let result = uploader.put(&url, b"data".to_vec(), "text/csv").await;
+
+        assert!(matches!(result, Err(UploadError::Rejected(_))));
+        assert!(received.lock().unwrap().is_empty());
+        assert!(matches!(
+            uploader.check_url("file:///etc/passwd"),
+            Err(UploadError::Rejected(_))
+        ));
+    }
+}
@@ -2,14 +2,244 @@ pub mod domain;
 pub mod application;
 pub mod infrastructure;
 pub mod presentation;
+pub mod config;
 
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use application::ports::{Authenticator, ExportService};
 use application::use_cases::ExportUseCase;
-use infrastructure::auth::JwtHandler;
+use config::AppConfig;
+use domain::validators::DefaultExportValidator;
+use infrastructure::auth::{ApiKeyAuthenticator, AuthScheme, JwtHandler};
+use infrastructure::jobs::JobStore;
+use infrastructure::metrics::ExportMetrics;
+use infrastructure::rate_limit::RateLimiter;
+use infrastructure::upload::{UploadError, Uploader};
+#[cfg(not(all(
+    feature = "csv",
+    feature = "excel",
+    feature = "pdf",
+    feature = "json",
+    feature = "html"
+)))]
+use domain::models::ExportFormat;
+#[cfg(not(all(
+    feature = "csv",
+    feature = "excel",
+    feature = "pdf",
+    feature = "json",
+    feature = "html"
+)))]
+use infrastructure::exporters::DisabledExporter;
+#[cfg(feature = "csv")]
+use infrastructure::exporters::CsvExporter;
+#[cfg(feature = "excel")]
+use infrastructure::exporters::ExcelExporter;
+#[cfg(feature = "json")]
+use infrastructure::exporters::JsonExporter;
+#[cfg(feature = "pdf")]
+use infrastructure::exporters::{GroupingNumberFormatter, PdfExporter, PdfLayoutConfig};
+#[cfg(feature = "html")]
+use infrastructure::exporters::HtmlExporter;
 
 /// Application state
 #[derive(Clone)]
 pub struct AppState {
+    pub config: Arc<AppConfig>,
     pub jwt_handler: Arc<JwtHandler>,
+    pub authenticator: Arc<dyn Authenticator>,
     pub use_case: Arc<ExportUseCase>,
+    pub rate_limiter: Arc<RateLimiter>,
+    pub uploader: Arc<Uploader>,
+    pub jobs: Arc<JobStore>,
+    pub metrics: Arc<ExportMetrics>,
+    /// When the service started, for the uptime reported by `/health`
+    pub started_at: Instant,
+}
+
+impl AppState {
+    /// Wire the validator, exporters, JWT handler and authenticator from configuration
+    ///
+    /// Fails only when the upload HTTP client cannot be built.
+    pub fn new(config: &AppConfig) -> Result<Self, UploadError> {
+        let jwt_handler =
+            JwtHandler::new(config.jwt_secret.clone(), config.jwt_expiration_seconds);
+        let jwt_handler = match &config.jwt_audience {
+            Some(audience) => jwt_handler.with_audience(audience.clone()),
+            None => jwt_handler,
+        };
+        let jwt_handler = Arc::new(match &config.jwt_issuer {
+            Some(issuer) => jwt_handler.with_issuer(issuer.clone()),
+            None => jwt_handler,
+        });
+        let authenticator: Arc<dyn Authenticator> = match config.auth_scheme {
+            AuthScheme::Jwt => jwt_handler.clone(),
+            AuthScheme::ApiKey => Arc::new(ApiKeyAuthenticator::new(&config.api_key)),
+        };
+
+        // Formats compiled out via Cargo features answer with a clear error
+        #[cfg(feature = "excel")]
+        let excel_service: Arc<dyn ExportService> =
+            Arc::new(ExcelExporter::new().with_number_formats(config.excel_formats.clone()));
+        #[cfg(not(feature = "excel"))]
+        let excel_service: Arc<dyn ExportService> =
+            Arc::new(DisabledExporter::new(ExportFormat::Excel));
+
+        #[cfg(feature = "csv")]
+        let csv_service: Arc<dyn ExportService> = Arc::new(CsvExporter);
+        #[cfg(not(feature = "csv"))]
+        let csv_service: Arc<dyn ExportService> =
+            Arc::new(DisabledExporter::new(ExportFormat::Csv));
+
+        #[cfg(feature = "pdf")]
+        let pdf_service: Arc<dyn ExportService> = {
+            let numbers =
+                GroupingNumberFormatter::new().with_currency_symbol(&config.pdf_currency_symbol);
+            let layout = PdfLayoutConfig {
+                footer_text: config.pdf_footer_text.clone(),
+                ..Default::default()
+            };
+            Arc::new(
+                PdfExporter::new()
+                    .config(layout)
+                    .number_formatter(Arc::new(numbers)),
+            )
+        };
+        #[cfg(not(feature = "pdf"))]
+        let pdf_service: Arc<dyn ExportService> =
+            Arc::new(DisabledExporter::new(ExportFormat::Pdf));
+
+        #[cfg(feature = "json")]
+        let json_service: Arc<dyn ExportService> = Arc::new(JsonExporter);
+        #[cfg(not(feature = "json"))]
+        let json_service: Arc<dyn ExportService> =
+            Arc::new(DisabledExporter::new(ExportFormat::Json));
+
+        #[cfg(feature = "html")]
+        let html_service: Arc<dyn ExportService> = Arc::new(HtmlExporter);
+        #[cfg(not(feature = "html"))]
+        let html_service: Arc<dyn ExportService> =
+            Arc::new(DisabledExporter::new(ExportFormat::Html));
+
+        let metrics = Arc::new(ExportMetrics::new());
+        let use_case = ExportUseCase::new(
+            Arc::new(DefaultExportValidator::new(config.validation)),
+            excel_service,
+            csv_service,
+            pdf_service,
+            json_service,
+            html_service,
+        )
+        .with_max_output_bytes(config.max_output_bytes)
+        .with_recorder(metrics.clone());
+        #[cfg(feature = "csv")]
+        let use_case = use_case.with_csv_streaming(Arc::new(CsvExporter));
+        let use_case = Arc::new(use_case);
+
+        Ok(Self {
+            config: Arc::new(config.clone()),
+            jwt_handler,
+            authenticator,
+            use_case,
+            rate_limiter: Arc::new(RateLimiter::new(
+                config.rate_limit_requests,
+                Duration::from_secs(config.rate_limit_window_seconds),
+            )),
+            uploader: Arc::new(Uploader::new(config.upload_allowed_hosts.clone())?),
+            jobs: Arc::new(JobStore::new(
+                Duration::from_secs(config.job_ttl_seconds),
+                Duration::from_secs(config.job_timeout_seconds),
+                config.max_jobs,
+            )),
+            metrics,
+            started_at: Instant::now(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn test_env_configured_row_limit_rejects_larger_exports() {
+        use super::*;
+        use domain::errors::DomainError;
+        use domain::models::{ExportData, ExportFormat};
+
+        std::env::set_var("MAX_EXPORT_ROWS", "2");
+        let config = AppConfig::from_env();
+        std::env::remove_var("MAX_EXPORT_ROWS");
+        let state = AppState::new(&config).unwrap();
+
+        let data = |rows: usize| ExportData {
+            title: "State".to_string(),
+            format: ExportFormat::Csv,
+            headers: vec!["Name".to_string()],
+            rows: vec![vec!["Widget".to_string()]; rows],
+            options: None,
+            column_metadata: None,
+            summary_row: None,
+            sheets: None,
+        };
+
+        assert_eq!(config.validation.max_rows, 2);
+        assert!(matches!(
+            state.use_case.execute(data(3)),
+            Err(DomainError::TooManyRows { count: 3, max: 2 })
+        ));
+        #[cfg(feature = "csv")]
+        assert!(state.use_case.execute(data(2)).is_ok());
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn test_app_state_from_config_exports_csv() {
+        use super::*;
+        use domain::models::{ExportData, ExportFormat};
+
+        let config = AppConfig {
+            jwt_expiration_seconds: 60,
+            ..Default::default()
+        };
+        let state = AppState::new(&config).unwrap();
+
+        let data = ExportData {
+            title: "State".to_string(),
+            format: ExportFormat::Csv,
+            headers: vec!["Name".to_string()],
+            rows: vec![vec!["Widget".to_string()]],
+            options: None,
+            column_metadata: None,
+            summary_row: None,
+            sheets: None,
+        };
+
+        let bytes = state.use_case.execute(data).unwrap();
+        assert_eq!(bytes, b"Name\nWidget\n");
+        assert_eq!(state.jwt_handler.expiration(), 60);
+    }
+
+    #[cfg(not(feature = "pdf"))]
+    #[test]
+    fn test_disabled_pdf_feature_rejects_pdf_requests() {
+        use super::*;
+        use domain::models::{ExportData, ExportFormat};
+
+        let state = AppState::new(&AppConfig::default()).unwrap();
+
+        let data = ExportData {
+            title: "State".to_string(),
+            format: ExportFormat::Pdf,
+            headers: vec!["Name".to_string()],
+            rows: vec![vec!["Widget".to_string()]],
+            options: None,
+            column_metadata: None,
+            summary_row: None,
+            sheets: None,
+        };
+
+        let err = state.use_case.execute(data).unwrap_err();
+        assert!(matches!(err, domain::errors::DomainError::InvalidFormat(_)));
+        assert!(err.to_string().contains("pdf export is not enabled"));
+    }
 }
@@ -1,45 +1,35 @@
 use axum::{
     extract::{Request, State},
-    http::{header, StatusCode},
+    http::StatusCode,
     middleware::Next,
     response::Response,
     Json,
 };
 use std::sync::Arc;
-use crate::infrastructure::auth::JwtHandler;
+use crate::domain::errors::DomainError;
+use crate::application::ports::Authenticator;
 
-/// Auth middleware
+/// Auth middleware, delegating to the configured authenticator
 pub async fn auth_middleware(
-    State(jwt_handler): State<Arc<JwtHandler>>,
-    request: Request,
+    State(authenticator): State<Arc<dyn Authenticator>>,
+    mut request: Request,
     next: Next,
 ) -> Result<Response, (StatusCode, Json<serde_json::Value>)> {
-    // Extract token from Authorization header
-    let token = request
-        .headers()
-        .get(header::AUTHORIZATION)
-        .and_then(|h| h.to_str().ok())
-        .and_then(|auth_header| auth_header.strip_prefix("Bearer "))
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(serde_json::json!({
-                    "error": "Unauthorized",
-                    "message": "Missing authorization token"
-                })),
-            )
-        })?;
-
-    // Validate token
-    jwt_handler.validate_token(token).map_err(|_| {
+    // Authenticate and hand the principal to inner layers
+    let principal = authenticator.authenticate(request.headers()).map_err(|e| {
+        let message = match e {
+            DomainError::MissingCredentials => "Missing authorization token",
+            _ => "Invalid or expired token",
+        };
         (
             StatusCode::UNAUTHORIZED,
             Json(serde_json::json!({
                 "error": "Unauthorized",
-                "message": "Invalid or expired token"
+                "message": message
             })),
         )
     })?;
+    request.extensions_mut().insert(principal);
 
     Ok(next.run(request).await)
 }
@@ -0,0 +1,44 @@
+use axum::{
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+/// Answer oversized request bodies with a JSON `413 Payload Too Large`
+///
+/// A declared `Content-Length` over `max_bytes` is rejected before the body is read;
+/// bodies without one are cut off by the route's `DefaultBodyLimit`, whose plain-text
+/// rejection is replaced here.
+pub async fn json_body_limit(
+    State(max_bytes): State<usize>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let declared = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+    if declared.is_some_and(|length| length > max_bytes as u64) {
+        return payload_too_large(max_bytes);
+    }
+
+    let response = next.run(request).await;
+    if response.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        return payload_too_large(max_bytes);
+    }
+    response
+}
+
+fn payload_too_large(max_bytes: usize) -> Response {
+    (
+        StatusCode::PAYLOAD_TOO_LARGE,
+        Json(serde_json::json!({
+            "error": "Payload Too Large",
+            "message": format!("Request body exceeds the limit of {} bytes", max_bytes)
+        })),
+    )
+        .into_response()
+}
@@ -0,0 +1,71 @@
+use std::io::Write;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+/// `Content-Encoding` value of gzip-compressed responses
+pub const GZIP: &str = "gzip";
+
+/// Whether an `Accept-Encoding` value allows gzip (listed without `q=0`)
+pub fn accepts_gzip(accept_encoding: Option<&str>) -> bool {
+    accept_encoding.is_some_and(|value| {
+        value.split(',').any(|coding| {
+            let mut params = coding.split(';').map(str::trim);
+            let name = params.next().unwrap_or_default();
+            let refused = params.any(|param| {
+                param
+                    .strip_prefix("q=")
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .is_some_and(|q| q == 0.0)
+            });
+            name.eq_ignore_ascii_case(GZIP) && !refused
+        })
+    })
+}
+
+/// Gzip `bytes` when the client accepts it, returning the body and its `Content-Encoding`
+pub fn maybe_compress(
+    bytes: Vec<u8>,
+    accept_encoding: Option<&str>,
+) -> (Vec<u8>, Option<&'static str>) {
+    if !accepts_gzip(accept_encoding) {
+        return (bytes, None);
+    }
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    // Writing to memory cannot fail, but fall back to the raw body rather than panic
+    match encoder.write_all(&bytes).and_then(|_| encoder.finish()) {
+        Ok(compressed) => (compressed, Some(GZIP)),
+        Err(_) => (bytes, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    #[test]
+    fn test_gzip_accepted_compresses_body() {
+        let body = b"Name,Amount\n".repeat(100);
+
+        let (compressed, encoding) = maybe_compress(body.clone(), Some("deflate, gzip;q=0.8, br"));
+
+        assert_eq!(encoding, Some("gzip"));
+        assert!(compressed.len() < body.len());
+        let mut decoded = Vec::new();
+        GzDecoder::new(compressed.as_slice())
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, body);
+    }
+
+    #[test]
+    fn test_body_is_unchanged_without_gzip() {
+        for accept_encoding in [None, Some("deflate, br"), Some("identity"), Some("gzip;q=0")] {
+            let (body, encoding) = maybe_compress(b"Name\n".to_vec(), accept_encoding);
+
+            assert_eq!(body, b"Name\n", "{:?}", accept_encoding);
+            assert_eq!(encoding, None, "{:?}", accept_encoding);
+        }
+    }
+}
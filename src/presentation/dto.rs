@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 /// Token response
 #[derive(Debug, Serialize, Deserialize)]
@@ -7,3 +8,130 @@ pub struct TokenResponse {
     pub expires_in: i64,
     pub token_type: String,
 }
+
+/// Query string of `GET /api/auth/token`
+#[derive(Debug, Default, Deserialize)]
+pub struct TokenQuery {
+    /// Configured client to issue the token for (required when `TOKEN_CLIENT_IDS` is set)
+    pub client_id: Option<String>,
+}
+
+/// Bulk token request
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TokensRequest {
+    pub count: usize,
+    /// Configured client to issue the tokens for (required when `TOKEN_CLIENT_IDS` is set)
+    #[serde(default)]
+    pub client_id: Option<String>,
+}
+
+/// Bulk token response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TokensResponse {
+    pub tokens: Vec<String>,
+    pub expires_in: i64,
+    pub token_type: String,
+}
+
+/// Bulk export request: the usual export body with a `formats` list instead of `format`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleRequest {
+    /// Format names (case-insensitive), each exported into the archive once
+    pub formats: Vec<String>,
+    /// Remaining `ExportRequest` fields, shared by every format
+    #[serde(flatten)]
+    pub export: Map<String, Value>,
+}
+
+/// How `POST /api/export` returns the file
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseMode {
+    /// Binary download (default)
+    #[default]
+    Binary,
+    /// JSON body with the file embedded as a base64 data URI
+    Json,
+}
+
+/// Diagnostics requested with `?debug=`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DebugMode {
+    /// Report validation and generation time in a `Server-Timing` header
+    Timing,
+}
+
+/// Export query parameters
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub response: ResponseMode,
+    #[serde(default)]
+    pub debug: Option<DebugMode>,
+}
+
+/// Query parameters of `POST /api/export/{job_id}/convert`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConvertQuery {
+    /// Format to export the job's data in
+    pub format: String,
+}
+
+/// Export returned as a data URI (`?response=json`)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DataUriResponse {
+    pub filename: String,
+    pub mime: String,
+    pub data: String,
+}
+
+/// Result of uploading an export to its `destination`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UploadResponse {
+    pub filename: String,
+    pub size: usize,
+    /// HTTP status returned by the destination
+    pub upload_status: u16,
+}
+
+/// Lifecycle state of an asynchronous export job
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobState {
+    Pending,
+    Done,
+    Failed,
+}
+
+/// Status of an asynchronous export job
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JobResponse {
+    pub job_id: String,
+    pub status: JobState,
+    /// Why the export failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Health check response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HealthResponse {
+    pub status: String,
+    /// Crate version of the running build
+    pub version: String,
+    /// Seconds since the application state was created
+    pub uptime_seconds: u64,
+}
+
+/// Export limits response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LimitsResponse {
+    pub max_rows: usize,
+    pub max_columns: usize,
+    pub max_cell_length: usize,
+    pub max_header_length: usize,
+    pub max_request_bytes: usize,
+    pub max_output_bytes: usize,
+    pub supported_formats: Vec<String>,
+}
@@ -0,0 +1,133 @@
+use chrono::{DateTime, Utc};
+use crate::domain::models::{ExportData, ExportFormat};
+
+/// Characters replaced with `_` in filenames (path separators, quotes, shell/OS reserved)
+const UNSAFE_CHARS: &[char] = &['/', '\\', '"', '\'', ':', '*', '?', '<', '>', '|', ';'];
+
+/// Longest client-requested filename kept, in characters, excluding the extension
+pub const MAX_FILENAME_CHARS: usize = 128;
+
+/// Build the download filename for an export from a template
+///
+/// Tokens: `{title}`, `{timestamp}` (Unix seconds), `{date}` (`YYYY-MM-DD`), `{format}`
+/// and `{sub}` (token subject, empty when unknown). The result is sanitized for use in a
+/// `Content-Disposition` header and on disk, then the format's extension is appended.
+pub fn export_filename(
+    template: &str,
+    data: &ExportData,
+    subject: Option<&str>,
+    now: DateTime<Utc>,
+) -> String {
+    let name = template
+        .replace("{title}", &data.title)
+        .replace("{timestamp}", &now.timestamp().to_string())
+        .replace("{date}", &now.format("%Y-%m-%d").to_string())
+        .replace("{format}", data.format.name())
+        .replace("{sub}", subject.unwrap_or_default());
+    format!("{}.{}", sanitize(&name), data.format.extension())
+}
+
+/// Sanitize a filename sent by the client, appending the format's extension if missing
+///
+/// Control characters are dropped, path separators and other unsafe characters become
+/// `_`, leading dots and underscores are removed (so `../` prefixes and hidden names do
+/// not survive) and the name is cut to `MAX_FILENAME_CHARS`. `None` when nothing usable
+/// is left.
+pub fn requested_filename(name: &str, format: ExportFormat) -> Option<String> {
+    let name: String = name.chars().filter(|c| !c.is_control()).collect();
+    let sanitized = sanitize(name.trim());
+    let suffix = format!(".{}", format.extension());
+    let stem_len = sanitized.len().saturating_sub(suffix.len());
+    let stem = match sanitized.get(stem_len..) {
+        Some(end) if end.eq_ignore_ascii_case(&suffix) => &sanitized[..stem_len],
+        _ => &sanitized,
+    };
+    let stem: String = stem
+        .trim_start_matches(['.', '_'])
+        .chars()
+        .take(MAX_FILENAME_CHARS)
+        .collect();
+    if stem.is_empty() {
+        return None;
+    }
+    Some(format!("{}{}", stem, suffix))
+}
+
+/// Replace whitespace, control and unsafe characters with `_`
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_whitespace() || c.is_control() || UNSAFE_CHARS.contains(&c) {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::ExportFormat;
+    use chrono::TimeZone;
+
+    fn report(title: &str) -> ExportData {
+        ExportData {
+            title: title.to_string(),
+            format: ExportFormat::Pdf,
+            headers: vec!["Name".to_string()],
+            rows: vec![],
+            options: None,
+            column_metadata: None,
+            summary_row: None,
+            sheets: None,
+        }
+    }
+
+    #[test]
+    fn test_default_template_matches_title_and_timestamp() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 25, 12, 0, 0).unwrap();
+        let filename = export_filename("{title}_{timestamp}", &report("Sales Report"), None, now);
+
+        assert_eq!(filename, "Sales_Report_1706184000.pdf");
+    }
+
+    #[test]
+    fn test_custom_template_is_resolved_and_sanitized() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 25, 12, 0, 0).unwrap();
+        let filename = export_filename(
+            "{sub}-{date}-{format}-{title}",
+            &report("Q1 \"Sales\"/EU"),
+            Some("acme:ops"),
+            now,
+        );
+
+        assert_eq!(filename, "acme_ops-2024-01-25-pdf-Q1__Sales__EU.pdf");
+    }
+
+    #[test]
+    fn test_requested_filename_cannot_escape_directory() {
+        let filename = requested_filename("../../etc/pass\nwd", ExportFormat::Csv).unwrap();
+
+        assert_eq!(filename, "etc_passwd.csv");
+        assert_eq!(requested_filename("..\\..\\", ExportFormat::Csv), None);
+        assert_eq!(requested_filename(" .. ", ExportFormat::Csv), None);
+    }
+
+    #[test]
+    fn test_requested_filename_gets_extension_once() {
+        assert_eq!(
+            requested_filename("Q1 report", ExportFormat::Pdf).unwrap(),
+            "Q1_report.pdf"
+        );
+        assert_eq!(requested_filename("q1.PDF", ExportFormat::Pdf).unwrap(), "q1.pdf");
+        assert_eq!(
+            requested_filename("q1.csv", ExportFormat::Excel).unwrap(),
+            "q1.csv.xlsx"
+        );
+
+        let long = requested_filename(&"a".repeat(500), ExportFormat::Json).unwrap();
+        assert_eq!(long, format!("{}.json", "a".repeat(MAX_FILENAME_CHARS)));
+    }
+}
@@ -1,83 +1,1874 @@
 use axum::{
     body::Body,
-    extract::State,
-    http::{header, StatusCode},
+    extract::{Extension, Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
 use crate::application::dto::ExportRequest;
+use crate::application::ports::Principal;
+use crate::application::use_cases::ExportTimings;
+use crate::infrastructure::exporters::enabled_formats;
+use crate::domain::errors::DomainError;
+use crate::domain::models::{ExportData, ExportFormat};
+use crate::infrastructure::auth::DEFAULT_SUBJECT;
+use crate::infrastructure::checksum::{sha256_hex, verify_sha256};
+use crate::infrastructure::jobs::{JobOutput, JobStatus};
+use crate::infrastructure::upload::UploadError;
+use crate::presentation::compression::{self, accepts_gzip, maybe_compress};
+use crate::presentation::filename::{export_filename, requested_filename};
+use crate::presentation::packaging::{self, package_bundle, package_download};
+use crate::presentation::streaming;
+use crate::presentation::dto::{
+    BundleRequest, ConvertQuery, DataUriResponse, DebugMode, ExportQuery, HealthResponse,
+    JobResponse, JobState, LimitsResponse, ResponseMode, TokenQuery, TokensRequest,
+    TokensResponse, UploadResponse,
+};
+use base64::prelude::{Engine, BASE64_STANDARD};
+use schemars::{schema::RootSchema, schema_for};
+
+/// Response header carrying the hex SHA-256 of the exported file
+pub const CHECKSUM_HEADER: header::HeaderName = header::HeaderName::from_static("x-content-sha256");
+
+/// Response header listing, as a JSON array, the titles of sheets dropped by `dedupe_sheets`
+pub const OMITTED_SHEETS_HEADER: header::HeaderName =
+    header::HeaderName::from_static("x-omitted-sheets");
+
+/// Response header listing, as a JSON array, the lossy fixes made to the data instead of
+/// rejecting it (e.g. truncated cells)
+pub const EXPORT_WARNINGS_HEADER: header::HeaderName =
+    header::HeaderName::from_static("x-export-warnings");
+
+/// Response header carrying phase durations when `?debug=timing` is set
+pub const SERVER_TIMING_HEADER: header::HeaderName =
+    header::HeaderName::from_static("server-timing");
 
 /// Health check endpoint
-pub async fn health_check() -> &'static str {
-    "OK"
+pub async fn health_check(State(state): State<crate::AppState>) -> Json<HealthResponse> {
+    Json(HealthResponse {
+        status: "ok".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        uptime_seconds: state.started_at.elapsed().as_secs(),
+    })
+}
+
+/// Export metrics in the Prometheus text format
+pub async fn get_metrics(State(state): State<crate::AppState>) -> Response {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(),
+    )
+        .into_response()
 }
 
 /// Get JWT token
 pub async fn get_token(
-    State(state): State<crate::AppState>
-) -> Json<crate::presentation::dto::TokenResponse> {
-    let token = state.jwt_handler.generate_token();
+    State(state): State<crate::AppState>,
+    Query(query): Query<TokenQuery>,
+) -> Response {
+    let subject = match token_subject(&state, query.client_id.as_deref()) {
+        Ok(subject) => subject,
+        Err(message) => return invalid_client(message),
+    };
+    let token = match state.jwt_handler.generate_token_for(subject) {
+        Ok(token) => token,
+        Err(e) => return token_failed(e),
+    };
     Json(crate::presentation::dto::TokenResponse {
         token,
         expires_in: state.jwt_handler.expiration(),
         token_type: "Bearer".to_string(),
     })
+    .into_response()
+}
+
+/// Subject to issue tokens for: the requested client id when client ids are configured
+///
+/// The subject keys the rate limit and owns export jobs, so it must not be something a
+/// caller can mint: without configured ids every token shares the default subject.
+fn token_subject<'a>(
+    state: &'a crate::AppState,
+    client_id: Option<&str>,
+) -> Result<&'a str, String> {
+    let clients = &state.config.token_client_ids;
+    if clients.is_empty() {
+        return Ok(DEFAULT_SUBJECT);
+    }
+    let id = client_id.ok_or_else(|| "client_id is required".to_string())?;
+    clients
+        .iter()
+        .find(|client| client.as_str() == id)
+        .map(String::as_str)
+        .ok_or_else(|| format!("Unknown client_id: {}", id))
+}
+
+fn invalid_client(message: String) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(serde_json::json!({
+            "error": "Invalid client",
+            "message": message
+        })),
+    )
+        .into_response()
+}
+
+fn token_failed(e: DomainError) -> Response {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(serde_json::json!({
+            "error": "Token generation failed",
+            "message": e.to_string()
+        })),
+    )
+        .into_response()
+}
+
+/// Largest number of tokens issued by one bulk request
+pub const MAX_BULK_TOKENS: usize = 100;
+
+/// Issue several tokens at once (e.g. for parallel CI workers)
+pub async fn get_tokens(
+    State(state): State<crate::AppState>,
+    Json(req): Json<TokensRequest>,
+) -> Response {
+    if req.count == 0 || req.count > MAX_BULK_TOKENS {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": "Invalid count",
+                "message": format!("count must be between 1 and {}", MAX_BULK_TOKENS)
+            })),
+        )
+            .into_response();
+    }
+
+    let subject = match token_subject(&state, req.client_id.as_deref()) {
+        Ok(subject) => subject,
+        Err(message) => return invalid_client(message),
+    };
+    let tokens = match (0..req.count)
+        .map(|_| state.jwt_handler.generate_token_for(subject))
+        .collect()
+    {
+        Ok(tokens) => tokens,
+        Err(e) => return token_failed(e),
+    };
+    Json(TokensResponse {
+        tokens,
+        expires_in: state.jwt_handler.expiration(),
+        token_type: "Bearer".to_string(),
+    })
+    .into_response()
+}
+
+/// Advertise the configured export limits
+pub async fn get_limits(State(state): State<crate::AppState>) -> Json<LimitsResponse> {
+    let limits = &state.config.validation;
+    Json(LimitsResponse {
+        max_rows: limits.max_rows,
+        max_columns: limits.max_columns,
+        max_cell_length: limits.max_cell_length,
+        max_header_length: limits.max_header_length,
+        max_request_bytes: state.config.max_request_bytes,
+        max_output_bytes: state.config.max_output_bytes,
+        supported_formats: enabled_formats()
+            .iter()
+            .map(|format| format.name().to_string())
+            .collect(),
+    })
+}
+
+/// JSON Schema describing the export request body
+pub async fn get_export_schema() -> Json<RootSchema> {
+    Json(schema_for!(ExportRequest))
 }
 
 /// Handle export request
 pub async fn handle_export(
     State(state): State<crate::AppState>,
+    Query(query): Query<ExportQuery>,
+    principal: Option<Extension<Principal>>,
+    headers: HeaderMap,
     Json(req): Json<ExportRequest>,
 ) -> Response {
     // Convert DTO to domain model
     let data = match req.to_domain() {
         Ok(d) => d,
-        Err(e) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({
-                    "error": "Invalid format",
-                    "message": e
-                })),
+        Err(e) => return invalid_format(e),
+    };
+
+    // Generate filename
+    let subject = principal
+        .as_ref()
+        .map(|Extension(principal)| principal.subject.as_str());
+    let filename = download_filename(&state, &req, &data, subject);
+    let disposition = content_disposition_value(&req, &filename);
+
+    if let Some(destination) = &req.destination {
+        if let Err(e) = state.uploader.check_url(&destination.put_url) {
+            return upload_failed(e);
+        }
+        let mime = data.format.mime_type();
+        let bytes = match state.use_case.execute(data) {
+            Ok(bytes) => bytes,
+            Err(e) => return export_failed(e),
+        };
+        let size = bytes.len();
+        return match state.uploader.put(&destination.put_url, bytes, mime).await
+        {
+            Ok(upload_status) => Json(UploadResponse {
+                filename,
+                size,
+                upload_status,
+            })
+            .into_response(),
+            Err(e) => upload_failed(e),
+        };
+    }
+
+    if query.response == ResponseMode::Json {
+        let mime = data.format.mime_type().to_string();
+        return match state.use_case.execute(data) {
+            Ok(bytes) => {
+                let data = format!("data:{};base64,{}", mime, BASE64_STANDARD.encode(bytes));
+                Json(DataUriResponse {
+                    filename,
+                    mime,
+                    data,
+                })
+                .into_response()
+            }
+            Err(e) => export_failed(e),
+        };
+    }
+
+    let accept_encoding = headers
+        .get(header::ACCEPT_ENCODING)
+        .and_then(|value| value.to_str().ok());
+
+    // Stream formats whose exporter can write incrementally (no checksum header); timing
+    // mode buffers instead so generation can be measured, and so does CSV zipping so the
+    // size can be compared with the threshold. Decided up front so data is prepared once.
+    let format = data.format;
+    let timing = query.debug == Some(DebugMode::Timing);
+    let zip_threshold = packaging::zip_threshold(state.config.csv_zip_threshold_bytes, format);
+    if !timing && zip_threshold.is_none() && state.use_case.streams(format) {
+        return match state.use_case.prepare_streaming(data) {
+            Ok((data, service, report)) => {
+                let gzip = accepts_gzip(accept_encoding);
+                let mut response = (
+                    StatusCode::OK,
+                    [
+                        (header::CONTENT_TYPE, format.mime_type()),
+                        (header::CONTENT_DISPOSITION, &disposition),
+                    ],
+                    streaming::export_body(service, data, gzip),
+                )
+                    .into_response();
+                set_content_encoding(&mut response, gzip.then_some(compression::GZIP));
+                set_json_list(&mut response, EXPORT_WARNINGS_HEADER, &report.warnings);
+                response
+            }
+            Err(e) => export_failed(e),
+        };
+    }
+
+    // Execute use case
+    match state.use_case.execute_reported(data) {
+        Ok((bytes, report)) => {
+            let package = match package_download(bytes, filename, format, zip_threshold) {
+                Ok(package) => package,
+                Err(e) => return export_failed(DomainError::InvalidFormat(e.to_string())),
+            };
+            let disposition = content_disposition_value(&req, &package.filename);
+            // The checksum covers the file itself, not its transfer encoding
+            let checksum = sha256_hex(&package.bytes);
+            let (bytes, encoding) = if package.zipped {
+                (package.bytes, None)
+            } else {
+                maybe_compress(package.bytes, accept_encoding)
+            };
+
+            // Return binary file
+            let mut response = (
+                StatusCode::OK,
+                [
+                    (header::CONTENT_TYPE, package.mime),
+                    (header::CONTENT_DISPOSITION, &disposition),
+                    (CHECKSUM_HEADER, &checksum),
+                ],
+                Body::from(bytes),
             )
                 .into_response();
+            set_content_encoding(&mut response, encoding);
+            set_json_list(&mut response, OMITTED_SHEETS_HEADER, &report.omitted_sheets);
+            set_json_list(&mut response, EXPORT_WARNINGS_HEADER, &report.warnings);
+            if timing {
+                if let Ok(value) = HeaderValue::from_str(&server_timing(&report.timings)) {
+                    response.headers_mut().insert(SERVER_TIMING_HEADER, value);
+                }
+            }
+            response
         }
+        Err(e) => export_failed(e),
+    }
+}
+
+/// The client's sanitized `filename`, or one built from the configured template
+fn download_filename(
+    state: &crate::AppState,
+    req: &ExportRequest,
+    data: &ExportData,
+    subject: Option<&str>,
+) -> String {
+    req.filename
+        .as_deref()
+        .and_then(|name| requested_filename(name, data.format))
+        .unwrap_or_else(|| {
+            export_filename(
+                &state.config.filename_template,
+                data,
+                subject,
+                chrono::Utc::now(),
+            )
+        })
+}
+
+/// Export one payload in several formats, returned together as a ZIP archive
+///
+/// Every format is checked and its data validated before any export runs. Entries are
+/// named after the sanitized title with each format's extension.
+pub async fn handle_bundle(
+    State(state): State<crate::AppState>,
+    principal: Option<Extension<Principal>>,
+    Json(req): Json<BundleRequest>,
+) -> Response {
+    let mut formats: Vec<ExportFormat> = Vec::new();
+    for name in &req.formats {
+        match ExportFormat::from_name(name).filter(|f| enabled_formats().contains(f)) {
+            Some(format) if !formats.contains(&format) => formats.push(format),
+            Some(_) => {}
+            None => return invalid_format(format!("Invalid format: {}", name)),
+        }
+    }
+    if formats.is_empty() {
+        return invalid_format("formats cannot be empty".to_string());
+    }
+
+    let mut exports = Vec::with_capacity(formats.len());
+    for format in formats {
+        let mut body = req.export.clone();
+        body.insert("format".to_string(), format.name().into());
+        let export: ExportRequest = match serde_json::from_value(body.into()) {
+            Ok(export) => export,
+            Err(e) => return invalid_request(e.to_string()),
+        };
+        if export.destination.is_some() {
+            return invalid_request("destination is not supported for bundles".to_string());
+        }
+        let data = match export.to_domain() {
+            Ok(d) => d,
+            Err(e) => return invalid_format(e),
+        };
+        if let Err(e) = state.use_case.validate(&data) {
+            return export_failed(e);
+        }
+        exports.push((export, data));
+    }
+
+    // The archive is named like a download of the first format
+    let subject = principal
+        .as_ref()
+        .map(|Extension(principal)| principal.subject.as_str());
+    let (requests, exports): (Vec<_>, Vec<_>) = exports.into_iter().unzip();
+    let filename = download_filename(&state, &requests[0], &exports[0], subject);
+    let archive_format = exports[0].format;
+
+    let mut files = Vec::with_capacity(exports.len());
+    let (mut omitted_sheets, mut warnings) = (Vec::new(), Vec::new());
+    for data in exports {
+        let name = requested_filename(&data.title, data.format)
+            .unwrap_or_else(|| format!("export.{}", data.format.extension()));
+        match state.use_case.execute_reported(data) {
+            Ok((bytes, report)) => {
+                files.push((name, bytes));
+                omitted_sheets.extend(report.omitted_sheets);
+                warnings.extend(report.warnings);
+            }
+            Err(e) => return export_failed(e),
+        }
+    }
+    let package = match package_bundle(&files, &filename, archive_format) {
+        Ok(package) => package,
+        Err(e) => return export_failed(DomainError::InvalidFormat(e.to_string())),
     };
+    let disposition = content_disposition_value(&requests[0], &package.filename);
+    let checksum = sha256_hex(&package.bytes);
 
-    // Execute use case
-    match state.use_case.execute(data.clone()) {
-        Ok(bytes) => {
-            // Generate filename
-            let filename = format!(
-                "{}_{}.{}",
-                data.title.replace(" ", "_"),
-                chrono::Utc::now().timestamp(),
-                data.format.extension()
-            );
+    let mut response = (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, package.mime),
+            (header::CONTENT_DISPOSITION, &disposition),
+            (CHECKSUM_HEADER, &checksum),
+        ],
+        Body::from(package.bytes),
+    )
+        .into_response();
+    set_json_list(&mut response, OMITTED_SHEETS_HEADER, &omitted_sheets);
+    set_json_list(&mut response, EXPORT_WARNINGS_HEADER, &warnings);
+    response
+}
 
-            // Return binary file
+/// Queue an export to run in the background, answering `202 Accepted` with its job id
+///
+/// The request is validated up front, so a queued job only fails if generation does.
+pub async fn submit_export_job(
+    State(state): State<crate::AppState>,
+    principal: Option<Extension<Principal>>,
+    Json(req): Json<ExportRequest>,
+) -> Response {
+    let data = match req.to_domain() {
+        Ok(d) => d,
+        Err(e) => return invalid_format(e),
+    };
+    if req.destination.is_some() {
+        return invalid_request("destination is not supported for async exports".to_string());
+    }
+    if let Err(e) = state.use_case.validate(&data) {
+        return export_failed(e);
+    }
+
+    let subject = principal
+        .as_ref()
+        .map(|Extension(principal)| principal.subject.as_str());
+    let filename = download_filename(&state, &req, &data, subject);
+    let disposition = content_disposition_value(&req, &filename);
+    let Some(id) = state.jobs.create(subject, data.clone()) else {
+        return jobs_full();
+    };
+
+    // Generation is CPU-bound, so it runs on the blocking pool rather than an async worker
+    let (jobs, use_case) = (state.jobs.clone(), state.use_case.clone());
+    let worker = tokio::task::spawn_blocking(move || {
+        let mime = data.format.mime_type();
+        use_case
+            .execute_reported(data)
+            .map(|(bytes, report)| JobOutput {
+                omitted_sheets: report.omitted_sheets,
+                warnings: report.warnings,
+                ..JobOutput::new(bytes, mime, disposition)
+            })
+            .map_err(|e| e.to_string())
+    });
+    // A panicking worker still ends the job, instead of leaving it pending
+    tokio::spawn(async move {
+        let result = worker
+            .await
+            .unwrap_or_else(|_| Err("Export failed unexpectedly".to_string()));
+        jobs.finish(id, result);
+    });
+
+    let location = format!("/api/export/jobs/{}", id);
+    (
+        StatusCode::ACCEPTED,
+        [(header::LOCATION, location)],
+        Json(JobResponse {
+            job_id: id.to_string(),
+            status: JobState::Pending,
+            message: None,
+        }),
+    )
+        .into_response()
+}
+
+/// Poll an export job: `202` while pending, the exported file once done, and the error
+/// message if it failed
+pub async fn get_export_job(
+    State(state): State<crate::AppState>,
+    Path(job_id): Path<String>,
+    principal: Option<Extension<Principal>>,
+) -> Response {
+    let subject = principal
+        .as_ref()
+        .map(|Extension(principal)| principal.subject.as_str());
+    let status = job_id
+        .parse::<uuid::Uuid>()
+        .ok()
+        .and_then(|id| state.jobs.status(id, subject));
+    let job_response = |status, message| {
+        Json(JobResponse {
+            job_id: job_id.clone(),
+            status,
+            message,
+        })
+    };
+
+    match status {
+        Some(JobStatus::Pending) => {
+            (StatusCode::ACCEPTED, job_response(JobState::Pending, None)).into_response()
+        }
+        Some(JobStatus::Failed(message)) => {
+            (StatusCode::OK, job_response(JobState::Failed, Some(message))).into_response()
+        }
+        // Stored output that no longer matches its checksum is never served
+        Some(JobStatus::Done(output)) if !verify_sha256(&output.bytes, &output.checksum) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({
+                "error": "Export corrupted",
+                "message": format!("Stored output of job {} failed its checksum", job_id)
+            })),
+        )
+            .into_response(),
+        Some(JobStatus::Done(output)) => {
+            let mut response = (
+                StatusCode::OK,
+                [
+                    (header::CONTENT_TYPE, output.mime),
+                    (header::CONTENT_DISPOSITION, &output.disposition),
+                    (CHECKSUM_HEADER, &output.checksum),
+                ],
+                Body::from(output.bytes.clone()),
+            )
+                .into_response();
+            set_json_list(&mut response, OMITTED_SHEETS_HEADER, &output.omitted_sheets);
+            set_json_list(&mut response, EXPORT_WARNINGS_HEADER, &output.warnings);
+            response
+        }
+        None => job_not_found(&job_id),
+    }
+}
+
+/// Export a job's source data again in another format (`?format=`), so clients need not
+/// re-send it
+///
+/// Works while the job is known, whether or not its own export has finished.
+pub async fn convert_export_job(
+    State(state): State<crate::AppState>,
+    Path(job_id): Path<String>,
+    Query(query): Query<ConvertQuery>,
+    principal: Option<Extension<Principal>>,
+) -> Response {
+    let subject = principal
+        .as_ref()
+        .map(|Extension(principal)| principal.subject.as_str());
+    let source = job_id
+        .parse::<uuid::Uuid>()
+        .ok()
+        .and_then(|id| state.jobs.source(id, subject));
+    let Some(source) = source else {
+        return job_not_found(&job_id);
+    };
+    let format = match ExportFormat::from_name(&query.format) {
+        Some(format) if enabled_formats().contains(&format) => format,
+        _ => return invalid_format(format!("Invalid format: {}", query.format)),
+    };
+
+    let data = ExportData {
+        format,
+        ..source.as_ref().clone()
+    };
+    let filename =
+        export_filename(&state.config.filename_template, &data, subject, chrono::Utc::now());
+    let disposition = format!("attachment; filename=\"{}\"", filename);
+    match state.use_case.execute(data) {
+        Ok(bytes) => {
+            let checksum = sha256_hex(&bytes);
             (
                 StatusCode::OK,
                 [
-                    (header::CONTENT_TYPE, data.format.mime_type()),
-                    (
-                        header::CONTENT_DISPOSITION,
-                        &format!("attachment; filename=\"{}\"", filename),
-                    ),
+                    (header::CONTENT_TYPE, format.mime_type()),
+                    (header::CONTENT_DISPOSITION, &disposition),
+                    (CHECKSUM_HEADER, &checksum),
                 ],
                 Body::from(bytes),
             )
                 .into_response()
         }
-        Err(e) => {
-            (
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({
-                    "error": "Export failed",
-                    "message": e.to_string()
-                })),
+        Err(e) => export_failed(e),
+    }
+}
+
+fn jobs_full() -> Response {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(serde_json::json!({
+            "error": "Too many jobs",
+            "message": "The export job queue is full, retry later"
+        })),
+    )
+        .into_response()
+}
+
+fn job_not_found(job_id: &str) -> Response {
+    (
+        StatusCode::NOT_FOUND,
+        Json(serde_json::json!({
+            "error": "Job not found",
+            "message": format!("No export job with id {}", job_id)
+        })),
+    )
+        .into_response()
+}
+
+/// Mark a response body as compressed with `encoding`; either way it varies by
+/// `Accept-Encoding`
+fn set_content_encoding(response: &mut Response, encoding: Option<&'static str>) {
+    let headers = response.headers_mut();
+    headers.insert(header::VARY, HeaderValue::from_static("accept-encoding"));
+    if let Some(encoding) = encoding {
+        headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static(encoding));
+    }
+}
+
+/// Set `name` to `values` as a JSON array; nothing is added when there are no values or
+/// they do not fit in a header
+fn set_json_list(response: &mut Response, name: header::HeaderName, values: &[String]) {
+    if values.is_empty() {
+        return;
+    }
+    let values = serde_json::json!(values).to_string();
+    if let Ok(value) = HeaderValue::from_bytes(values.as_bytes()) {
+        response.headers_mut().insert(name, value);
+    }
+}
+
+/// Format phase durations as a `Server-Timing` value in milliseconds
+fn server_timing(timings: &ExportTimings) -> String {
+    let ms = |duration: std::time::Duration| duration.as_secs_f64() * 1000.0;
+    format!(
+        "validation;dur={}, generation;dur={}",
+        ms(timings.validation),
+        ms(timings.generation)
+    )
+}
+
+fn upload_failed(e: UploadError) -> Response {
+    let status = match e {
+        UploadError::Rejected(_) => StatusCode::BAD_REQUEST,
+        UploadError::Request(_) => StatusCode::BAD_GATEWAY,
+        UploadError::Client(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (
+        status,
+        Json(serde_json::json!({
+            "error": "Upload failed",
+            "message": e.to_string()
+        })),
+    )
+        .into_response()
+}
+
+/// `Content-Disposition` value for a download of `filename`; the request's disposition
+/// has already been checked by `to_domain`
+fn content_disposition_value(req: &ExportRequest, filename: &str) -> String {
+    let disposition = req.disposition().unwrap_or_default();
+    format!("{}; filename=\"{}\"", disposition.as_str(), filename)
+}
+
+fn invalid_request(message: String) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(serde_json::json!({
+            "error": "Invalid request",
+            "message": message
+        })),
+    )
+        .into_response()
+}
+
+fn invalid_format(message: String) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(serde_json::json!({
+            "error": "Invalid format",
+            "message": message
+        })),
+    )
+        .into_response()
+}
+
+fn export_failed(e: DomainError) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(serde_json::json!({
+            "error": "Export failed",
+            "message": e.to_string()
+        })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "pdf")]
+    use crate::application::dto::Disposition;
+    use crate::config::AppConfig;
+    use crate::domain::validators::ValidationLimits;
+    use crate::presentation::routes::router;
+    use crate::AppState;
+    use axum::body::to_bytes;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    fn test_state() -> AppState {
+        AppState::new(&AppConfig::default()).unwrap()
+    }
+
+    #[cfg(feature = "pdf")]
+    fn test_request(disposition: Disposition) -> ExportRequest {
+        ExportRequest {
+            title: "Sales Report".to_string(),
+            format: "pdf".to_string(),
+            headers: vec!["Name".to_string(), "Amount".to_string()],
+            rows: vec![vec!["Widget".to_string(), "100".to_string()]],
+            has_header: None,
+            options: None,
+            column_metadata: None,
+            column_types: None,
+            summary_row: None,
+            sheets: None,
+            disposition: Some(disposition.as_str().to_string()),
+            filename: None,
+            destination: None,
+        }
+    }
+
+    #[cfg(any(feature = "csv", feature = "pdf"))]
+    fn content_disposition(response: &Response) -> &str {
+        response
+            .headers()
+            .get(header::CONTENT_DISPOSITION)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+    }
+
+    #[cfg(feature = "csv")]
+    #[tokio::test]
+    async fn test_export_defaults_to_attachment() {
+        let req: ExportRequest = serde_json::from_value(serde_json::json!({
+            "title": "Sales Report",
+            "format": "csv",
+            "headers": ["Name"],
+            "rows": [["Widget"]]
+        }))
+        .unwrap();
+
+        let response = handle_export(
+            State(test_state()),
+            Query(ExportQuery::default()),
+            None,
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(content_disposition(&response).starts_with("attachment; filename=\"Sales_Report_"));
+    }
+
+    #[cfg(feature = "excel")]
+    #[tokio::test]
+    async fn test_duplicate_sheets_are_reported_when_deduped() {
+        let sheet = |title: &str| {
+            serde_json::json!({"title": title, "headers": ["Item"], "rows": [["Widget"]]})
+        };
+        let body = serde_json::json!({
+            "title": "Workbook",
+            "format": "excel",
+            "headers": [],
+            "rows": [],
+            "sheets": [sheet("Q1"), sheet("Q1 copy")],
+            "options": {"dedupe_sheets": true}
+        });
+        let req: ExportRequest = serde_json::from_value(body.clone()).unwrap();
+
+        let response = handle_export(
+            State(test_state()),
+            Query(ExportQuery::default()),
+            None,
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[OMITTED_SHEETS_HEADER], r#"["Q1 copy"]"#);
+
+        // Bundles and finished async jobs report them too
+        let mut bundle = body.clone();
+        bundle["formats"] = serde_json::json!(["excel"]);
+        let bundle: BundleRequest = serde_json::from_value(bundle).unwrap();
+        let response = handle_bundle(State(test_state()), None, Json(bundle)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[OMITTED_SHEETS_HEADER], r#"["Q1 copy"]"#);
+
+        let state = test_state();
+        let req: ExportRequest = serde_json::from_value(body).unwrap();
+        let submitted = submit_export_job(State(state.clone()), None, Json(req)).await;
+        assert_eq!(submitted.status(), StatusCode::ACCEPTED);
+        let body = to_bytes(submitted.into_body(), usize::MAX).await.unwrap();
+        let job: JobResponse = serde_json::from_slice(&body).unwrap();
+        let mut done = None;
+        for _ in 0..100 {
+            let response =
+                get_export_job(State(state.clone()), Path(job.job_id.clone()), None).await;
+            if response.status() == StatusCode::OK {
+                done = Some(response);
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let done = done.expect("job did not finish");
+        assert_eq!(done.headers()[OMITTED_SHEETS_HEADER], r#"["Q1 copy"]"#);
+    }
+
+    #[cfg(feature = "csv")]
+    #[tokio::test]
+    async fn test_requested_filename_is_used_for_download() {
+        let req: ExportRequest = serde_json::from_value(serde_json::json!({
+            "title": "Sales Report",
+            "format": "csv",
+            "headers": ["Name"],
+            "rows": [["Widget"]],
+            "filename": "../q1 report"
+        }))
+        .unwrap();
+
+        let response = handle_export(
+            State(test_state()),
+            Query(ExportQuery::default()),
+            None,
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            content_disposition(&response),
+            "attachment; filename=\"q1_report.csv\""
+        );
+    }
+
+    #[cfg(feature = "csv")]
+    #[tokio::test]
+    async fn test_streamed_csv_matches_buffered_export() {
+        use crate::application::ports::ExportService;
+        use crate::infrastructure::exporters::CsvExporter;
+
+        let rows: Vec<Vec<String>> = (0..2000)
+            .map(|i| {
+                vec![
+                    i.to_string(),
+                    format!("Item {}", i),
+                    "a, \"quoted\" note".to_string(),
+                ]
+            })
+            .collect();
+        let req: ExportRequest = serde_json::from_value(serde_json::json!({
+            "title": "Large Report",
+            "format": "csv",
+            "headers": ["Id", "Name", "Note"],
+            "rows": rows
+        }))
+        .unwrap();
+        let buffered = CsvExporter.export(&req.to_domain().unwrap()).unwrap();
+
+        let response = handle_export(
+            State(test_state()),
+            Query(ExportQuery::default()),
+            None,
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get(CHECKSUM_HEADER).is_none());
+        let streamed = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(buffered.len() > 8 * 1024);
+        assert_eq!(streamed, buffered);
+    }
+
+    #[cfg(feature = "csv")]
+    #[tokio::test]
+    async fn test_gzip_accepting_client_gets_compressed_export() {
+        use flate2::read::GzDecoder;
+        use std::io::Read;
+
+        let request = || -> ExportRequest {
+            serde_json::from_value(serde_json::json!({
+                "title": "Sales Report",
+                "format": "csv",
+                "headers": ["Name"],
+                "rows": vec![["Widget"]; 200]
+            }))
+            .unwrap()
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT_ENCODING, "gzip, deflate".parse().unwrap());
+        // Streamed, and buffered because timing is measured
+        let queries = [
+            ExportQuery::default(),
+            ExportQuery {
+                debug: Some(DebugMode::Timing),
+                ..Default::default()
+            },
+        ];
+
+        for query in queries {
+            let response = handle_export(
+                State(test_state()),
+                Query(query),
+                None,
+                headers.clone(),
+                Json(request()),
             )
-                .into_response()
+            .await;
+
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+            assert_eq!(response.headers()[header::VARY], "accept-encoding");
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let mut csv = String::new();
+            GzDecoder::new(&body[..]).read_to_string(&mut csv).unwrap();
+            assert_eq!(csv, format!("Name\n{}", "Widget\n".repeat(200)));
+        }
+
+        let plain = handle_export(
+            State(test_state()),
+            Query(ExportQuery::default()),
+            None,
+            HeaderMap::new(),
+            Json(request()),
+        )
+        .await;
+        assert!(plain.headers().get(header::CONTENT_ENCODING).is_none());
+    }
+
+    #[cfg(feature = "pdf")]
+    #[tokio::test]
+    async fn test_export_inline_disposition() {
+        let response = handle_export(
+            State(test_state()),
+            Query(ExportQuery::default()),
+            None,
+            HeaderMap::new(),
+            Json(test_request(Disposition::Inline)),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let value = content_disposition(&response);
+        assert!(
+            value.starts_with("inline; filename=\"Sales_Report_"),
+            "{}",
+            value
+        );
+        assert!(value.ends_with(".pdf\""), "{}", value);
+    }
+
+    #[cfg(feature = "csv")]
+    #[tokio::test]
+    async fn test_disposition_sets_header_and_rejects_unknown_values() {
+        let export = |disposition: Option<&str>| {
+            let mut req = serde_json::json!({
+                "title": "Sales Report",
+                "format": "csv",
+                "headers": ["Name"],
+                "rows": [["Widget"]],
+                "filename": "sales"
+            });
+            if let Some(disposition) = disposition {
+                req["disposition"] = disposition.into();
+            }
+            handle_export(
+                State(test_state()),
+                Query(ExportQuery::default()),
+                None,
+                HeaderMap::new(),
+                Json(serde_json::from_value(req).unwrap()),
+            )
+        };
+
+        for (disposition, expected) in [
+            (None, "attachment; filename=\"sales.csv\""),
+            (Some("attachment"), "attachment; filename=\"sales.csv\""),
+            (Some("Inline"), "inline; filename=\"sales.csv\""),
+        ] {
+            let response = export(disposition).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(content_disposition(&response), expected);
+        }
+
+        let response = export(Some("download")).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["message"], "Invalid disposition: download");
+    }
+
+    #[cfg(feature = "pdf")]
+    #[tokio::test]
+    async fn test_export_returns_checksum_of_body() {
+        let response = handle_export(
+            State(test_state()),
+            Query(ExportQuery::default()),
+            None,
+            HeaderMap::new(),
+            Json(test_request(Disposition::Attachment)),
+        )
+        .await;
+
+        let checksum = response.headers()[CHECKSUM_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(checksum, sha256_hex(&body));
+    }
+
+    #[cfg(feature = "csv")]
+    #[tokio::test]
+    async fn test_debug_timing_reports_both_phases() {
+        let req: ExportRequest = serde_json::from_value(serde_json::json!({
+            "title": "Sales Report",
+            "format": "csv",
+            "headers": ["Name"],
+            "rows": [["Widget"]]
+        }))
+        .unwrap();
+        let query = ExportQuery {
+            debug: Some(DebugMode::Timing),
+            ..Default::default()
+        };
+
+        let response = handle_export(
+            State(test_state()),
+            Query(query),
+            None,
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let timing = response.headers()[SERVER_TIMING_HEADER].to_str().unwrap();
+        let durations: Vec<(&str, f64)> = timing
+            .split(", ")
+            .map(|metric| {
+                let (name, dur) = metric.split_once(";dur=").unwrap();
+                (name, dur.parse().unwrap())
+            })
+            .collect();
+        assert_eq!(durations.len(), 2, "{}", timing);
+        assert_eq!(durations[0].0, "validation");
+        assert_eq!(durations[1].0, "generation");
+        assert!(durations.iter().all(|(_, dur)| *dur > 0.0), "{}", timing);
+        // Timing mode buffers the export, so the checksum is present even for CSV
+        assert!(response.headers().get(CHECKSUM_HEADER).is_some());
+    }
+
+    #[cfg(feature = "csv")]
+    #[tokio::test]
+    async fn test_csv_over_zip_threshold_is_zipped() {
+        use std::io::Read;
+
+        let state = AppState::new(&AppConfig {
+            csv_zip_threshold_bytes: Some(64),
+            ..Default::default()
+        })
+        .unwrap();
+        let request = |rows: usize| -> ExportRequest {
+            serde_json::from_value(serde_json::json!({
+                "title": "Sales Report",
+                "format": "csv",
+                "headers": ["Name"],
+                "rows": vec![["Widget"]; rows]
+            }))
+            .unwrap()
+        };
+
+        let small = handle_export(
+            State(state.clone()),
+            Query(ExportQuery::default()),
+            None,
+            HeaderMap::new(),
+            Json(request(2)),
+        )
+        .await;
+        assert_eq!(small.headers()[header::CONTENT_TYPE], "text/csv");
+        assert!(content_disposition(&small).ends_with(".csv\""));
+
+        let large = handle_export(
+            State(state),
+            Query(ExportQuery::default()),
+            None,
+            HeaderMap::new(),
+            Json(request(20)),
+        )
+        .await;
+        assert_eq!(large.status(), StatusCode::OK);
+        assert_eq!(large.headers()[header::CONTENT_TYPE], "application/zip");
+        let disposition = content_disposition(&large).to_string();
+        assert!(disposition.starts_with("attachment; filename=\"Sales_Report_"));
+        assert!(disposition.ends_with(".zip\""), "{}", disposition);
+
+        let body = to_bytes(large.into_body(), usize::MAX).await.unwrap();
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(body.to_vec())).unwrap();
+        assert_eq!(archive.len(), 1);
+        let mut entry = archive.by_index(0).unwrap();
+        let zip_name = disposition.trim_end_matches(".zip\"").rsplit('"').next().unwrap();
+        assert_eq!(entry.name(), format!("{}.csv", zip_name));
+        let mut csv = String::new();
+        entry.read_to_string(&mut csv).unwrap();
+        assert_eq!(csv, format!("Name\n{}", "Widget\n".repeat(20)));
+    }
+
+    #[cfg(feature = "csv")]
+    #[tokio::test]
+    async fn test_metrics_count_csv_export_without_auth() {
+        let state = test_state();
+        let req: ExportRequest = serde_json::from_value(serde_json::json!({
+            "title": "Sales Report",
+            "format": "csv",
+            "headers": ["Name"],
+            "rows": [["Widget"]]
+        }))
+        .unwrap();
+        let response = handle_export(
+            State(state.clone()),
+            Query(ExportQuery::default()),
+            None,
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await;
+        // Streamed exports are recorded once the body has been written
+        to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        let response = router(state)
+            .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let content_type = response.headers()[header::CONTENT_TYPE].to_str().unwrap();
+        assert!(content_type.starts_with("text/plain"));
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        let csv_exports = text
+            .lines()
+            .find_map(|line| line.strip_prefix("exports_total{format=\"csv\"} "))
+            .and_then(|count| count.parse::<u64>().ok())
+            .unwrap();
+        assert!(csv_exports >= 1);
+        assert!(text.contains("# TYPE export_duration_seconds histogram"));
+    }
+
+    #[cfg(feature = "csv")]
+    #[tokio::test]
+    async fn test_repairs_are_reported_in_header_and_metrics() {
+        let state = AppState::new(&AppConfig {
+            validation: ValidationLimits {
+                max_cell_length: 4,
+                overlong_cells: crate::domain::validators::OverlongCellMode::Truncate,
+                ..ValidationLimits::default()
+            },
+            ..AppConfig::default()
+        })
+        .unwrap();
+        let req: ExportRequest = serde_json::from_value(serde_json::json!({
+            "title": "Sales Report",
+            "format": "csv",
+            "headers": ["Name"],
+            "rows": [["Widget"]]
+        }))
+        .unwrap();
+
+        let response = handle_export(
+            State(state.clone()),
+            Query(ExportQuery::default()),
+            None,
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[EXPORT_WARNINGS_HEADER],
+            r#"["truncated 6-byte cell in row 1 to 4 bytes"]"#
+        );
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, "Name\nWidg\n");
+        assert!(state.metrics.render().contains("export_repairs_total{format=\"csv\"} 1\n"));
+    }
+
+    #[tokio::test]
+    async fn test_health_reports_version_without_auth() {
+        let response = router(test_state())
+            .oneshot(Request::get("/health").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let health: HealthResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(health.status, "ok");
+        assert_eq!(health.version, env!("CARGO_PKG_VERSION"));
+        assert!(health.uptime_seconds < 60);
+    }
+
+    #[tokio::test]
+    async fn test_limits_endpoint_reflects_config() {
+        let config = AppConfig {
+            validation: ValidationLimits {
+                max_rows: 500,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let app = router(AppState::new(&config).unwrap());
+
+        let response = app
+            .oneshot(
+                Request::get("/api/export/limits")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let limits: LimitsResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(limits.max_rows, 500);
+        assert_eq!(limits.max_cell_length, 1000);
+        assert_eq!(limits.max_request_bytes, config.max_request_bytes);
+        assert_eq!(limits.max_output_bytes, config.max_output_bytes);
+        #[cfg(all(
+            feature = "csv",
+            feature = "excel",
+            feature = "pdf",
+            feature = "json",
+            feature = "html"
+        ))]
+        assert_eq!(limits.supported_formats, vec!["excel", "csv", "pdf", "json", "html", "tsv"]);
+    }
+
+    #[tokio::test]
+    async fn test_token_signing_failure_is_500() {
+        let config = AppConfig {
+            jwt_secret: String::new(),
+            ..Default::default()
+        };
+
+        let state = AppState::new(&config).unwrap();
+        let response = get_token(State(state), Query(TokenQuery::default())).await;
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["error"], "Token generation failed");
+    }
+
+    #[tokio::test]
+    async fn test_bulk_tokens_are_distinct_and_valid() {
+        let state = test_state();
+        let request = TokensRequest {
+            count: 5,
+            client_id: None,
+        };
+        let response = get_tokens(State(state.clone()), Json(request)).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let issued: TokensResponse = serde_json::from_slice(&body).unwrap();
+        let distinct: std::collections::HashSet<_> = issued.tokens.iter().collect();
+        assert_eq!(issued.tokens.len(), 5);
+        assert_eq!(distinct.len(), 5);
+        for token in &issued.tokens {
+            assert!(state.jwt_handler.validate_token(token).is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tokens_are_issued_for_configured_clients_only() {
+        let state = AppState::new(&AppConfig {
+            token_client_ids: vec!["reports".to_string(), "billing".to_string()],
+            ..AppConfig::default()
+        })
+        .unwrap();
+        let token_for = |client_id: Option<&str>| {
+            let query = TokenQuery {
+                client_id: client_id.map(str::to_string),
+            };
+            get_token(State(state.clone()), Query(query))
+        };
+
+        // Every token for a client carries the same subject, so refreshing a token
+        // neither resets the client's quota nor loses its jobs
+        for _ in 0..2 {
+            let response = token_for(Some("reports")).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let issued: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let token = issued["token"].as_str().unwrap();
+            assert_eq!(state.jwt_handler.validate_token(token).unwrap().sub, "reports");
+        }
+        assert_eq!(token_for(Some("stranger")).await.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(token_for(None).await.status(), StatusCode::BAD_REQUEST);
+
+        let request = TokensRequest {
+            count: 3,
+            client_id: Some("stranger".to_string()),
+        };
+        let response = get_tokens(State(state.clone()), Json(request)).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_tokens_rejects_too_large_count() {
+        let request = TokensRequest {
+            count: MAX_BULK_TOKENS + 1,
+            client_id: None,
+        };
+        let response = get_tokens(State(test_state()), Json(request)).await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[cfg(feature = "csv")]
+    #[tokio::test]
+    async fn test_json_response_mode_returns_data_uri() {
+        let request = || -> ExportRequest {
+            serde_json::from_value(serde_json::json!({
+                "title": "Sales Report",
+                "format": "csv",
+                "headers": ["Name", "Amount"],
+                "rows": [["Widget", "100"]]
+            }))
+            .unwrap()
+        };
+        let json_query = ExportQuery {
+            response: ResponseMode::Json,
+            ..Default::default()
+        };
+
+        let binary = handle_export(
+            State(test_state()),
+            Query(ExportQuery::default()),
+            None,
+            HeaderMap::new(),
+            Json(request()),
+        )
+        .await;
+        let binary = to_bytes(binary.into_body(), usize::MAX).await.unwrap();
+        let response = handle_export(
+            State(test_state()),
+            Query(json_query),
+            None,
+            HeaderMap::new(),
+            Json(request()),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: DataUriResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json.mime, "text/csv");
+        assert!(json.filename.starts_with("Sales_Report_") && json.filename.ends_with(".csv"));
+        let payload = json.data.strip_prefix("data:text/csv;base64,").unwrap();
+        assert_eq!(BASE64_STANDARD.decode(payload).unwrap(), binary);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_body_gets_json_413() {
+        let config = AppConfig {
+            max_request_bytes: 1024,
+            ..Default::default()
+        };
+        let state = AppState::new(&config).unwrap();
+        let token = state.jwt_handler.generate_token().unwrap();
+        let rows = vec![vec!["Widget".to_string()]; 200];
+        let body = serde_json::json!({
+            "title": "Bulk",
+            "format": "csv",
+            "headers": ["Name"],
+            "rows": rows
+        })
+        .to_string();
+
+        // Rejected from the declared length, and while reading a body without one
+        for declared in [true, false] {
+            let mut request = Request::post("/api/export")
+                .header(header::AUTHORIZATION, format!("Bearer {}", token))
+                .header(header::CONTENT_TYPE, "application/json");
+            if declared {
+                request = request.header(header::CONTENT_LENGTH, body.len());
+            }
+            let response = router(state.clone())
+                .oneshot(request.body(Body::from(body.clone())).unwrap())
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(error["error"], "Payload Too Large");
+            assert_eq!(error["message"], "Request body exceeds the limit of 1024 bytes");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_rejects_non_json_content_type() {
+        let state = test_state();
+        let token = state.jwt_handler.generate_token().unwrap();
+        let app = router(state);
+
+        let response = app
+            .oneshot(
+                Request::post("/api/export")
+                    .header(header::AUTHORIZATION, format!("Bearer {}", token))
+                    .header(header::CONTENT_TYPE, "text/plain")
+                    .body(Body::from(r#"{"title":"x"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["error"], "Unsupported Media Type");
+    }
+
+    #[tokio::test]
+    async fn test_upload_to_disallowed_host_is_400() {
+        let req: ExportRequest = serde_json::from_value(serde_json::json!({
+            "title": "Sales Report",
+            "format": "csv",
+            "headers": ["Name"],
+            "rows": [["Widget"]],
+            "destination": { "put_url": "http://169.254.169.254/latest/meta-data" }
+        }))
+        .unwrap();
+
+        let response = handle_export(
+            State(test_state()),
+            Query(ExportQuery::default()),
+            None,
+            HeaderMap::new(),
+            Json(req),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["error"], "Upload failed");
+        assert!(error["message"].as_str().unwrap().contains("host not allowed"));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_headers_and_429() {
+        let state = AppState::new(&AppConfig {
+            rate_limit_requests: 2,
+            ..Default::default()
+        })
+        .unwrap();
+        let token = state.jwt_handler.generate_token().unwrap();
+        let app = router(state);
+
+        let mut responses = Vec::new();
+        for _ in 0..3 {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::post("/api/export")
+                        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+                        .header(header::CONTENT_TYPE, "application/json")
+                        .body(Body::from(r#"{"title":"x"}"#))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            responses.push(response);
         }
+
+        let header_value = |response: &Response, name: &str| {
+            response.headers()[name].to_str().unwrap().to_string()
+        };
+        let remaining: Vec<String> = responses
+            .iter()
+            .map(|r| header_value(r, "x-ratelimit-remaining"))
+            .collect();
+        assert_eq!(remaining, vec!["1", "0", "0"]);
+        assert_ne!(responses[1].status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(responses[2].status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(header_value(&responses[2], "x-ratelimit-limit"), "2");
+        let reset: u64 = header_value(&responses[2], "x-ratelimit-reset").parse().unwrap();
+        assert!((1..=60).contains(&reset));
+
+        let body = to_bytes(responses.remove(2).into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["error"], "Too Many Requests");
+    }
+
+    #[cfg(feature = "csv")]
+    #[tokio::test]
+    async fn test_api_key_scheme_authenticates_exports() {
+        use crate::infrastructure::auth::{AuthScheme, API_KEY_HEADER};
+
+        let state = AppState::new(&AppConfig {
+            auth_scheme: AuthScheme::ApiKey,
+            api_key: "s3cret-key".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        let token = state.jwt_handler.generate_token().unwrap();
+        let app = router(state);
+        let body = r#"{"title":"x","format":"csv","headers":["Name"],"rows":[["Widget"]]}"#;
+        let export = |name: header::HeaderName, value: String| {
+            Request::post("/api/export")
+                .header(name, value)
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let valid = app
+            .clone()
+            .oneshot(export(API_KEY_HEADER, "s3cret-key".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(valid.status(), StatusCode::OK);
+
+        let invalid = app
+            .clone()
+            .oneshot(export(API_KEY_HEADER, "wrong-key".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(invalid.status(), StatusCode::UNAUTHORIZED);
+
+        // JWTs are not accepted once the deployment switches to API keys
+        let jwt = app
+            .oneshot(export(header::AUTHORIZATION, format!("Bearer {}", token)))
+            .await
+            .unwrap();
+        assert_eq!(jwt.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[cfg(feature = "csv")]
+    #[tokio::test]
+    async fn test_async_export_job_completes_with_file() {
+        let state = test_state();
+        let token = state.jwt_handler.generate_token().unwrap();
+        let app = router(state);
+        let bearer = format!("Bearer {}", token);
+        let body = r#"{"title":"Report","format":"csv","headers":["Name"],"rows":[["Widget"]]}"#;
+
+        let submitted = app
+            .clone()
+            .oneshot(
+                Request::post("/api/export/async")
+                    .header(header::AUTHORIZATION, &bearer)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(submitted.status(), StatusCode::ACCEPTED);
+        let location = submitted.headers()[header::LOCATION].to_str().unwrap().to_string();
+        let body = to_bytes(submitted.into_body(), usize::MAX).await.unwrap();
+        let job: JobResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(job.status, JobState::Pending);
+        assert_eq!(location, format!("/api/export/jobs/{}", job.job_id));
+
+        let poll = || {
+            Request::get(location.as_str())
+                .header(header::AUTHORIZATION, &bearer)
+                .body(Body::empty())
+                .unwrap()
+        };
+        let mut done = None;
+        for _ in 0..100 {
+            let response = app.clone().oneshot(poll()).await.unwrap();
+            if response.status() == StatusCode::OK {
+                done = Some(response);
+                break;
+            }
+            assert_eq!(response.status(), StatusCode::ACCEPTED);
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let done = done.expect("job did not finish");
+        assert_eq!(done.headers()[header::CONTENT_TYPE], "text/csv");
+        assert!(content_disposition(&done).starts_with("attachment; filename=\"Report_"));
+        let checksum = done.headers()[CHECKSUM_HEADER].to_str().unwrap().to_string();
+        let body = to_bytes(done.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, "Name\nWidget\n");
+        assert_eq!(checksum, sha256_hex(&body));
+    }
+
+    #[tokio::test]
+    async fn test_async_export_rejects_invalid_request_before_queueing() {
+        let state = test_state();
+        let token = state.jwt_handler.generate_token().unwrap();
+        let app = router(state.clone());
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::post("/api/export/async")
+                    .header(header::AUTHORIZATION, format!("Bearer {}", token))
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(
+                        r#"{"title":"Report","format":"csv","headers":["Name"],"rows":[]}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // Unknown ids, and jobs submitted by another subject, are not found
+        let source = ExportData::builder()
+            .format(ExportFormat::Csv)
+            .headers(["Name"])
+            .add_row(["Widget"])
+            .build()
+            .unwrap();
+        let other = state.jobs.create(Some("someone-else"), source).unwrap();
+        for id in [uuid::Uuid::new_v4().to_string(), other.to_string(), "nope".to_string()] {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::get(format!("/api/export/jobs/{}", id))
+                        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND, "{}", id);
+        }
+    }
+
+    #[cfg(feature = "csv")]
+    #[tokio::test]
+    async fn test_async_export_is_refused_when_job_store_is_full() {
+        let state = AppState::new(&AppConfig {
+            max_jobs: 1,
+            ..AppConfig::default()
+        })
+        .unwrap();
+        let token = state.jwt_handler.generate_token().unwrap();
+        let app = router(state);
+        let submit = || {
+            Request::post("/api/export/async")
+                .header(header::AUTHORIZATION, format!("Bearer {}", token))
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(
+                    r#"{"title":"Report","format":"csv","headers":["Name"],"rows":[["Widget"]]}"#,
+                ))
+                .unwrap()
+        };
+
+        let first = app.clone().oneshot(submit()).await.unwrap();
+        assert_eq!(first.status(), StatusCode::ACCEPTED);
+        // The first job is held, pending or finished, until it expires
+        let second = app.oneshot(submit()).await.unwrap();
+        assert_eq!(second.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = to_bytes(second.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["error"], "Too many jobs");
+    }
+
+    #[tokio::test]
+    async fn test_corrupted_job_output_is_not_served() {
+        let state = test_state();
+        let token = state.jwt_handler.generate_token().unwrap();
+        let claims = state.jwt_handler.validate_token(&token).unwrap();
+        let source = ExportData::builder()
+            .format(ExportFormat::Csv)
+            .headers(["Name"])
+            .add_row(["Widget"])
+            .build()
+            .unwrap();
+        let id = state.jobs.create(Some(&claims.sub), source).unwrap();
+        let output = JobOutput::new(b"Name\nWidget\n".to_vec(), "text/csv", String::new());
+        state.jobs.finish(id, Ok(output));
+        state.jobs.corrupt_output(id, b"Name\nWidgeX\n".to_vec());
+
+        let response = router(state)
+            .oneshot(
+                Request::get(format!("/api/export/jobs/{}", id))
+                    .header(header::AUTHORIZATION, format!("Bearer {}", token))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"], "Export corrupted");
+    }
+
+    #[cfg(all(feature = "csv", feature = "pdf"))]
+    #[tokio::test]
+    async fn test_csv_job_converts_to_pdf() {
+        let state = test_state();
+        let token = state.jwt_handler.generate_token().unwrap();
+        let app = router(state);
+        let bearer = format!("Bearer {}", token);
+        let body = r#"{"title":"Report","format":"csv","headers":["Name"],"rows":[["Widget"]]}"#;
+
+        let submitted = app
+            .clone()
+            .oneshot(
+                Request::post("/api/export/async")
+                    .header(header::AUTHORIZATION, &bearer)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = to_bytes(submitted.into_body(), usize::MAX).await.unwrap();
+        let job: JobResponse = serde_json::from_slice(&body).unwrap();
+
+        let converted = app
+            .oneshot(
+                Request::post(format!("/api/export/{}/convert?format=pdf", job.job_id))
+                    .header(header::AUTHORIZATION, &bearer)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(converted.status(), StatusCode::OK);
+        assert_eq!(converted.headers()[header::CONTENT_TYPE], "application/pdf");
+        assert!(content_disposition(&converted).ends_with(".pdf\""));
+        let body = to_bytes(converted.into_body(), usize::MAX).await.unwrap();
+        assert!(body.starts_with(b"%PDF"));
+    }
+
+    #[tokio::test]
+    async fn test_convert_unknown_job_is_not_found() {
+        let state = test_state();
+        let token = state.jwt_handler.generate_token().unwrap();
+        let app = router(state);
+
+        let response = app
+            .oneshot(
+                Request::post(format!("/api/export/{}/convert?format=pdf", uuid::Uuid::new_v4()))
+                    .header(header::AUTHORIZATION, format!("Bearer {}", token))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    fn bundle_request(formats: &[&str]) -> BundleRequest {
+        serde_json::from_value(serde_json::json!({
+            "title": "Sales",
+            "formats": formats,
+            "headers": ["Name", "Amount"],
+            "rows": [["Widget", "100"]]
+        }))
+        .unwrap()
+    }
+
+    #[cfg(all(feature = "csv", feature = "pdf"))]
+    #[tokio::test]
+    async fn test_bundle_zips_each_format() {
+        use std::io::Read;
+
+        let response = handle_bundle(
+            State(test_state()),
+            None,
+            Json(bundle_request(&["csv", "PDF", "csv"])),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/zip");
+        let disposition = content_disposition(&response).to_string();
+        assert!(disposition.starts_with("attachment; filename=\"Sales_"), "{}", disposition);
+        assert!(disposition.ends_with(".zip\""), "{}", disposition);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(body.to_vec())).unwrap();
+        let names: Vec<&str> = archive.file_names().collect();
+        assert_eq!(archive.len(), 2);
+        assert!(names.contains(&"Sales.csv") && names.contains(&"Sales.pdf"), "{:?}", names);
+        let mut csv = String::new();
+        archive
+            .by_name("Sales.csv")
+            .unwrap()
+            .read_to_string(&mut csv)
+            .unwrap();
+        assert_eq!(csv, "Name,Amount\nWidget,100\n");
+        let mut pdf = Vec::new();
+        archive.by_name("Sales.pdf").unwrap().read_to_end(&mut pdf).unwrap();
+        assert!(pdf.starts_with(b"%PDF"));
+    }
+
+    #[tokio::test]
+    async fn test_bundle_rejects_unknown_format_up_front() {
+        for formats in [vec!["csv", "xml"], vec![]] {
+            let response = handle_bundle(
+                State(test_state()),
+                None,
+                Json(bundle_request(&formats)),
+            )
+            .await;
+
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{:?}", formats);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_schema_endpoint_describes_request() {
+        let app = router(test_state());
+
+        let response = app
+            .oneshot(
+                Request::get("/api/export/schema")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let schema: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            schema["properties"]["format"]["enum"],
+            serde_json::json!(["excel", "csv", "pdf", "json", "html", "tsv"])
+        );
+        assert_eq!(
+            schema["required"],
+            serde_json::json!(["format", "headers", "rows", "title"])
+        );
+        assert_eq!(
+            schema["definitions"]["ColumnType"]["enum"],
+            serde_json::json!(["text", "number", "currency", "percentage", "date", "qrcode"])
+        );
     }
 }
@@ -1,3 +1,11 @@
 pub mod handlers;
 pub mod auth;
+pub mod content_type;
+pub mod rate_limit;
 pub mod dto;
+pub mod filename;
+pub mod packaging;
+pub mod routes;
+pub mod streaming;
+pub mod compression;
+pub mod body_limit;
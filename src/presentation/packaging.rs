@@ -0,0 +1,112 @@
+use zip::result::ZipResult;
+use crate::domain::models::ExportFormat;
+use crate::infrastructure::archive::{zip_files, zip_single_file};
+
+/// MIME type of ZIP archives
+pub const ZIP_MIME: &str = "application/zip";
+
+/// An exported file as it is sent for download
+#[derive(Debug)]
+pub struct Package {
+    pub bytes: Vec<u8>,
+    pub mime: &'static str,
+    /// Download filename, `.zip` when the file was archived
+    pub filename: String,
+    /// Whether `bytes` is a ZIP archive, which is not worth compressing again
+    pub zipped: bool,
+}
+
+/// The size above which downloads of `format` are zipped, from the configured CSV threshold
+pub fn zip_threshold(csv_threshold: Option<usize>, format: ExportFormat) -> Option<usize> {
+    csv_threshold.filter(|_| format == ExportFormat::Csv)
+}
+
+/// Send `bytes` as-is, or as a single-entry ZIP archive when larger than `zip_threshold`
+pub fn package_download(
+    bytes: Vec<u8>,
+    filename: String,
+    format: ExportFormat,
+    zip_threshold: Option<usize>,
+) -> ZipResult<Package> {
+    match zip_threshold {
+        Some(threshold) if bytes.len() > threshold => Ok(Package {
+            bytes: zip_single_file(&filename, &bytes)?,
+            mime: ZIP_MIME,
+            filename: archive_name(&filename, format),
+            zipped: true,
+        }),
+        _ => Ok(Package {
+            bytes,
+            mime: format.mime_type(),
+            filename,
+            zipped: false,
+        }),
+    }
+}
+
+/// Archive `files` (entry name and contents) together, named like a download of `format`
+/// called `filename`
+pub fn package_bundle(
+    files: &[(String, Vec<u8>)],
+    filename: &str,
+    format: ExportFormat,
+) -> ZipResult<Package> {
+    let entries: Vec<(&str, &[u8])> = files
+        .iter()
+        .map(|(name, bytes)| (name.as_str(), bytes.as_slice()))
+        .collect();
+    Ok(Package {
+        bytes: zip_files(&entries)?,
+        mime: ZIP_MIME,
+        filename: archive_name(filename, format),
+        zipped: true,
+    })
+}
+
+/// `filename` with the extension of `format` replaced by `.zip`
+fn archive_name(filename: &str, format: ExportFormat) -> String {
+    let extension = format!(".{}", format.extension());
+    let stem = filename.strip_suffix(&extension).unwrap_or(filename);
+    format!("{}.zip", stem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_download_at_or_below_threshold_is_unchanged() {
+        let bytes = b"a,b\n".to_vec();
+        let package =
+            package_download(bytes, "report.csv".to_string(), ExportFormat::Csv, Some(4)).unwrap();
+        assert!(!package.zipped);
+        assert_eq!(package.bytes, b"a,b\n");
+        assert_eq!(package.mime, ExportFormat::Csv.mime_type());
+        assert_eq!(package.filename, "report.csv");
+    }
+
+    #[test]
+    fn test_download_above_threshold_is_zipped() {
+        let bytes = b"a,b\n".to_vec();
+        let package =
+            package_download(bytes, "report.csv".to_string(), ExportFormat::Csv, Some(3)).unwrap();
+        assert!(package.zipped);
+        assert!(package.bytes.starts_with(b"PK"));
+        assert_eq!(package.mime, ZIP_MIME);
+        assert_eq!(package.filename, "report.zip");
+    }
+
+    #[test]
+    fn test_zip_threshold_only_applies_to_csv() {
+        assert_eq!(zip_threshold(Some(10), ExportFormat::Csv), Some(10));
+        assert_eq!(zip_threshold(Some(10), ExportFormat::Json), None);
+    }
+
+    #[test]
+    fn test_archive_name_replaces_only_a_trailing_extension() {
+        assert_eq!(archive_name("report.csv", ExportFormat::Csv), "report.zip");
+        assert_eq!(archive_name("report.json", ExportFormat::Json), "report.zip");
+        assert_eq!(archive_name("report.csv.csv", ExportFormat::Csv), "report.csv.zip");
+        assert_eq!(archive_name("report", ExportFormat::Csv), "report.zip");
+    }
+}
@@ -0,0 +1,57 @@
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::sync::Arc;
+use crate::application::ports::Principal;
+use crate::infrastructure::rate_limit::{RateLimitDecision, RateLimiter};
+
+const LIMIT_HEADER: HeaderName = HeaderName::from_static("x-ratelimit-limit");
+const REMAINING_HEADER: HeaderName = HeaderName::from_static("x-ratelimit-remaining");
+const RESET_HEADER: HeaderName = HeaderName::from_static("x-ratelimit-reset");
+
+/// Per-subject rate limit; must run inside `auth_middleware`, which supplies the principal
+///
+/// Both allowed and throttled responses carry the `X-RateLimit-*` quota headers.
+pub async fn rate_limit(
+    State(limiter): State<Arc<RateLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let subject = request
+        .extensions()
+        .get::<Principal>()
+        .map(|principal| principal.subject.clone())
+        .unwrap_or_default();
+    let decision = limiter.check(&subject);
+
+    let mut response = if decision.allowed {
+        next.run(request).await
+    } else {
+        (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(serde_json::json!({
+                "error": "Too Many Requests",
+                "message": format!(
+                    "Rate limit exceeded, retry in {} seconds",
+                    decision.reset_after
+                ),
+                "limit": decision.limit,
+                "remaining": decision.remaining,
+                "reset": decision.reset_after
+            })),
+        )
+            .into_response()
+    };
+    insert_quota_headers(response.headers_mut(), &decision);
+    response
+}
+
+fn insert_quota_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+    headers.insert(LIMIT_HEADER, HeaderValue::from(decision.limit));
+    headers.insert(REMAINING_HEADER, HeaderValue::from(decision.remaining));
+    headers.insert(RESET_HEADER, HeaderValue::from(decision.reset_after));
+}
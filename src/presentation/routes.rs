@@ -0,0 +1,76 @@
+use axum::{
+    extract::DefaultBodyLimit,
+    handler::Handler,
+    middleware,
+    routing::{get, post, MethodRouter},
+    Router,
+};
+use tower_http::cors::CorsLayer;
+use super::auth::auth_middleware;
+use super::body_limit::json_body_limit;
+use super::content_type::require_json;
+use super::rate_limit::rate_limit;
+use super::handlers::{
+    convert_export_job, get_export_job, get_export_schema, get_limits, get_metrics, get_token,
+    get_tokens, handle_bundle, handle_export, health_check, submit_export_job,
+};
+use crate::AppState;
+
+/// POST route for a JSON body: authenticated, rate limited, `application/json` only and
+/// capped at `max_request_bytes`
+fn guarded_json_post<H, T>(state: &AppState, handler: H) -> MethodRouter<AppState>
+where
+    H: Handler<T, AppState>,
+    T: 'static,
+{
+    post(handler.layer(DefaultBodyLimit::max(state.config.max_request_bytes)))
+        .layer(middleware::from_fn_with_state(
+            state.config.max_request_bytes,
+            json_body_limit,
+        ))
+        .layer(middleware::from_fn(require_json))
+        .layer(middleware::from_fn_with_state(
+            state.rate_limiter.clone(),
+            rate_limit,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.authenticator.clone(),
+            auth_middleware,
+        ))
+}
+
+/// Build the HTTP router with all routes and middleware
+pub fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/health", get(health_check))
+        .route("/metrics", get(get_metrics))
+        .route("/api/auth/token", get(get_token))
+        .route("/api/auth/tokens", post(get_tokens))
+        .route("/api/export/limits", get(get_limits))
+        .route("/api/export/schema", get(get_export_schema))
+        .route("/api/export", guarded_json_post(&state, handle_export))
+        .route("/api/export/bundle", guarded_json_post(&state, handle_bundle))
+        .route("/api/export/async", guarded_json_post(&state, submit_export_job))
+        // Polling is not rate limited; only submissions count against the quota
+        .route(
+            "/api/export/jobs/:id",
+            get(get_export_job).layer(middleware::from_fn_with_state(
+                state.authenticator.clone(),
+                auth_middleware,
+            )),
+        )
+        .route(
+            "/api/export/:id/convert",
+            post(convert_export_job)
+                .layer(middleware::from_fn_with_state(
+                    state.rate_limiter.clone(),
+                    rate_limit,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    state.authenticator.clone(),
+                    auth_middleware,
+                )),
+        )
+        .layer(CorsLayer::permissive())
+        .with_state(state)
+}
@@ -0,0 +1,68 @@
+use axum::body::Body;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::{self, BufWriter, Write};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use crate::application::ports::StreamingExportService;
+use crate::domain::models::ExportData;
+
+/// Bytes buffered before a chunk is handed to the response body
+const CHUNK_BYTES: usize = 8 * 1024;
+
+/// Chunks in flight before the exporter waits for the client to catch up
+const CHANNEL_CAPACITY: usize = 8;
+
+type Chunk = io::Result<Vec<u8>>;
+
+/// `Write` sink that forwards each write as a chunk over a channel
+struct ChannelWriter {
+    sender: mpsc::Sender<Chunk>,
+}
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.sender
+            .blocking_send(Ok(buf.to_vec()))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "client disconnected"))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Run a streaming exporter on the blocking pool and stream its output as the body
+///
+/// Data must already be validated; an exporter failure aborts the body mid-stream.
+/// With `gzip`, the output is compressed as it is written.
+pub fn export_body(service: Arc<dyn StreamingExportService>, data: ExportData, gzip: bool) -> Body {
+    let (sender, receiver) = mpsc::channel::<Chunk>(CHANNEL_CAPACITY);
+
+    tokio::task::spawn_blocking(move || {
+        let channel = ChannelWriter {
+            sender: sender.clone(),
+        };
+        let mut writer = BufWriter::with_capacity(CHUNK_BYTES, channel);
+        let export = |sink: &mut dyn Write| {
+            service
+                .export_to(&data, sink)
+                .map_err(|e| e.to_string())
+        };
+        let result = if gzip {
+            let mut encoder = GzEncoder::new(&mut writer, Compression::default());
+            export(&mut encoder).and_then(|_| encoder.try_finish().map_err(|e| e.to_string()))
+        } else {
+            export(&mut writer)
+        };
+        let result = result.and_then(|_| writer.flush().map_err(|e| e.to_string()));
+
+        if let Err(message) = result {
+            let _ = sender.blocking_send(Err(io::Error::other(message)));
+        }
+    });
+
+    Body::from_stream(ReceiverStream::new(receiver))
+}